@@ -9,6 +9,8 @@ fn main() -> Result<()> {
         "innertube/creator.proto",
         "innertube/context.proto",
         "innertube/flag.proto",
+        "innertube/search.proto",
+        "innertube/player.proto",
     ];
 
     let proto_paths = ["."];
@@ -0,0 +1,255 @@
+use rand::Rng;
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AddressError {
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+    #[error("Invalid prefix length: {0}")]
+    InvalidPrefixLength(String),
+    #[error("Unsupported IPv6 prefix length /{0}, expected /32 through /64")]
+    UnsupportedPrefixLength(u8),
+    #[error("{range_bits} range id bits don't fit in a /{prefix_len}, at most {max} are available")]
+    InvalidRangeBits { prefix_len: u8, range_bits: u8, max: u8 },
+    #[error("Range id {range_id} doesn't fit in {range_bits} bits")]
+    RangeIdOutOfBounds { range_id: u16, range_bits: u8 },
+    #[error("No IPv6 prefix configured")]
+    NoIpv6Prefix,
+    #[error("No addresses configured")]
+    Empty,
+    #[error("IPv4 and IPv6 addresses can't be mixed in one allocator")]
+    MixedFamilies,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Ipv6Prefix {
+    network: u128,
+    prefix_len: u8,
+    range_bits: u8,
+}
+
+impl Ipv6Prefix {
+    fn host_bits(&self) -> u8 {
+        128 - self.prefix_len - self.range_bits
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Ipv4Block {
+    network: u32,
+    prefix_len: u8,
+}
+
+impl Ipv4Block {
+    // Network and broadcast addresses are skipped in blocks larger than a /31
+    fn first_host(&self) -> u32 {
+        if self.prefix_len >= 31 { self.network } else { self.network + 1 }
+    }
+
+    fn host_count(&self) -> u64 {
+        let size = 1u64 << (32 - self.prefix_len);
+        if self.prefix_len >= 31 { size } else { size - 2 }
+    }
+}
+
+fn low_mask(bits: u8) -> u128 {
+    if bits == 0 { 0 } else { u128::MAX >> (128 - bits as u32) }
+}
+
+fn split_cidr(cidr: &str) -> Result<(&str, Option<u8>), AddressError> {
+    match cidr.trim().split_once('/') {
+        Some((address, prefix_len)) => {
+            let prefix_len = prefix_len.parse()
+                .map_err(|_| AddressError::InvalidPrefixLength(prefix_len.to_string()))?;
+            Ok((address, Some(prefix_len)))
+        }
+        None => Ok((cidr.trim(), None)),
+    }
+}
+
+/// Picks source addresses for outgoing connections.
+///
+/// An IPv6 prefix from /32 through /64 is split into three parts: the prefix itself, a "range id"
+/// selecting a sub-block (by default a /64, or a /48 for prefixes shorter than /48) and random
+/// host bits. Instead of a prefix, IPv4 addresses and CIDR blocks can be added. One allocator
+/// holds either, never both.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressAllocator {
+    ipv6: Option<Ipv6Prefix>,
+    ipv4: Vec<Ipv4Block>,
+}
+
+impl AddressAllocator {
+    pub fn new() -> Self {
+        AddressAllocator::default()
+    }
+
+    pub fn ipv6(subnet: &str) -> Result<Self, AddressError> {
+        AddressAllocator::new().with_ipv6(subnet)
+    }
+
+    /// Sets the IPv6 prefix, e.g. "2001:db8::/48". The range id covers the bits between the prefix
+    /// and the /64 boundary (at most 16), so a /48 has 65536 ranges and a /64 a single one.
+    pub fn with_ipv6(mut self, subnet: &str) -> Result<Self, AddressError> {
+        if !self.ipv4.is_empty() {
+            return Err(AddressError::MixedFamilies);
+        }
+
+        let (address, prefix_len) = split_cidr(subnet)?;
+        let prefix_len = prefix_len.ok_or_else(|| AddressError::InvalidPrefixLength(subnet.to_string()))?;
+        let address: Ipv6Addr = address.parse()
+            .map_err(|_| AddressError::InvalidAddress(address.to_string()))?;
+
+        if !(32..=64).contains(&prefix_len) {
+            return Err(AddressError::UnsupportedPrefixLength(prefix_len));
+        }
+
+        let network = u128::from(address) & !low_mask(128 - prefix_len);
+        self.ipv6 = Some(Ipv6Prefix {
+            network,
+            prefix_len,
+            range_bits: (64 - prefix_len).min(16),
+        });
+        Ok(self)
+    }
+
+    /// Changes how many bits after the prefix make up the range id (0 through 16).
+    /// The remaining bits are random.
+    pub fn with_range_bits(mut self, range_bits: u8) -> Result<Self, AddressError> {
+        let prefix = self.ipv6.as_mut().ok_or(AddressError::NoIpv6Prefix)?;
+        let max = (128 - prefix.prefix_len).min(16);
+        if range_bits > max {
+            return Err(AddressError::InvalidRangeBits { prefix_len: prefix.prefix_len, range_bits, max });
+        }
+
+        prefix.range_bits = range_bits;
+        Ok(self)
+    }
+
+    /// Adds an IPv4 address ("203.0.113.7") or block ("203.0.113.0/29"). Fails if an IPv6 prefix
+    /// is set.
+    pub fn with_ipv4(mut self, cidr: &str) -> Result<Self, AddressError> {
+        if self.ipv6.is_some() {
+            return Err(AddressError::MixedFamilies);
+        }
+
+        let (address, prefix_len) = split_cidr(cidr)?;
+        let prefix_len = prefix_len.unwrap_or(32);
+        let address: Ipv4Addr = address.parse()
+            .map_err(|_| AddressError::InvalidAddress(address.to_string()))?;
+
+        if prefix_len > 32 {
+            return Err(AddressError::InvalidPrefixLength(prefix_len.to_string()));
+        }
+
+        let network = u32::from(address) & !(low_mask(32 - prefix_len) as u32);
+        self.ipv4.push(Ipv4Block { network, prefix_len });
+        Ok(self)
+    }
+
+    pub fn has_ipv6(&self) -> bool {
+        self.ipv6.is_some()
+    }
+
+    pub fn range_bits(&self) -> u8 {
+        self.ipv6.map(|prefix| prefix.range_bits).unwrap_or_default()
+    }
+
+    /// Number of distinct range ids, 1 if the range id takes no bits.
+    pub fn range_count(&self) -> u32 {
+        1 << self.range_bits()
+    }
+
+    /// Number of usable IPv4 addresses across every block.
+    pub fn ipv4_count(&self) -> u64 {
+        self.ipv4.iter().map(|block| block.host_count()).sum()
+    }
+
+    /// A random address within the given range of the IPv6 prefix.
+    pub fn ipv6_address(&self, range_id: u16) -> Result<IpAddr, AddressError> {
+        let prefix = self.ipv6.ok_or(AddressError::NoIpv6Prefix)?;
+        if u32::from(range_id) >= 1 << prefix.range_bits {
+            return Err(AddressError::RangeIdOutOfBounds { range_id, range_bits: prefix.range_bits });
+        }
+
+        let host_bits = prefix.host_bits();
+        let range_part = (range_id as u128) << host_bits;
+        let host_part = rand::thread_rng().gen::<u128>() & low_mask(host_bits);
+
+        Ok(IpAddr::V6((prefix.network | range_part | host_part).into()))
+    }
+
+    /// A random address out of the IPv4 blocks.
+    pub fn ipv4_address(&self) -> Result<IpAddr, AddressError> {
+        let count = self.ipv4_count();
+        if count == 0 {
+            return Err(AddressError::Empty);
+        }
+
+        self.nth_ipv4(rand::thread_rng().gen_range(0..count))
+            .map(|(address, _)| address)
+            .ok_or(AddressError::Empty)
+    }
+
+    // The block index doubles as the range id of IPv4 addresses
+    fn nth_ipv4(&self, mut index: u64) -> Option<(IpAddr, u16)> {
+        for (block_index, block) in self.ipv4.iter().enumerate() {
+            if index < block.host_count() {
+                let address = Ipv4Addr::from(block.first_host() + index as u32);
+                return Some((IpAddr::V4(address), block_index as u16));
+            }
+            index -= block.host_count();
+        }
+        None
+    }
+
+    /// A random address, from a random range of the IPv6 prefix or out of the IPv4 blocks.
+    pub fn random_address(&self) -> Result<IpAddr, AddressError> {
+        match self.ipv6 {
+            Some(_) => {
+                let range_id = rand::thread_rng().gen_range(0..self.range_count()) as u16;
+                self.ipv6_address(range_id)
+            }
+            None => self.ipv4_address(),
+        }
+    }
+
+    /// Up to `count` distinct addresses with their range id.
+    ///
+    /// IPv6 addresses are spread over as many random ranges as possible. IPv4 addresses are returned
+    /// in order, with the index of their block as range id.
+    pub fn addresses(&self, count: usize) -> Result<Vec<(IpAddr, u16)>, AddressError> {
+        if self.ipv6.is_none() {
+            if self.ipv4.is_empty() {
+                return Err(AddressError::Empty);
+            }
+
+            let count = (count as u64).min(self.ipv4_count());
+            return Ok((0..count).filter_map(|index| self.nth_ipv4(index)).collect());
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut range_ids = HashSet::new();
+        while range_ids.len() < count.min(self.range_count() as usize) {
+            range_ids.insert(rng.gen_range(0..self.range_count()) as u16);
+        }
+        let range_ids: Vec<u16> = range_ids.into_iter().collect();
+
+        let mut seen = HashSet::new();
+        let mut addresses = Vec::with_capacity(count);
+        for index in 0..count {
+            let range_id = range_ids[index % range_ids.len()];
+            let address = loop {
+                let address = self.ipv6_address(range_id)?;
+                if seen.insert(address) {
+                    break address;
+                }
+            };
+            addresses.push((address, range_id));
+        }
+
+        Ok(addresses)
+    }
+}
@@ -1,16 +1,13 @@
 use crate::models::*;
-use crate::{youtube, InnerTubeRequestFields, YouTubeError};
-use hyper_tls::HttpsConnector;
-use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
 use hyper::body::Bytes;
-use http_body_util::{BodyExt, Full};
 use hyper::{Method, Request, StatusCode};
 use prost::Message;
 use scylla::frame::value::CqlTimestamp;
 use std::collections::HashSet;
 
-const ALL_COUNTRIES: &[&str] = &[
+pub(crate) const ALL_COUNTRIES: &[&str] = &[
     "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
     "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
     "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
@@ -29,26 +26,64 @@ const ALL_COUNTRIES: &[&str] = &[
     "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW"
 ];
 
-pub struct GetChannelRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+// e.g. "This account has been terminated due to multiple or severe violations of YouTube's policy against spam, deceptive practices, and misleading content or other Terms of Service violations."
+fn parse_termination_reason(text: &str) -> TerminationReason {
+    let lower = text.to_lowercase();
+
+    // Associated terminations also mention why the linked account was terminated, so they're checked first
+    if lower.contains("linked to") || lower.contains("associated with") || lower.contains("closely related") {
+        TerminationReason::AssociatedAccount
+    } else if lower.contains("spam") || lower.contains("deceptive") {
+        TerminationReason::Spam
+    } else if lower.contains("community guidelines") {
+        TerminationReason::CommunityGuidelines
+    } else if lower.contains("copyright") {
+        TerminationReason::Copyright
+    } else if lower.contains("legal complaint") {
+        TerminationReason::LegalComplaint
+    } else if lower.contains("terms of service") {
+        TerminationReason::TermsOfService
+    } else {
+        TerminationReason::Other(text.to_string())
+    }
+}
+
+pub(crate) fn parse_channel_status(alert_text: &str) -> ChannelStatus {
+    match alert_text {
+        "This channel is not available." => ChannelStatus::Hidden,
+        "This channel does not exist." => ChannelStatus::Deleted,
+        text if text.contains("terminated") => ChannelStatus::Terminated {
+            reason: parse_termination_reason(text)
+        },
+        other => ChannelStatus::Unknown(other.to_string()),
+    }
+}
+
+pub struct GetChannelRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub channel_id: String,
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for GetChannelRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetChannelRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
 
-impl<'a> GetChannelRequest<'a> {
+impl<T: Transport> GetChannelRequest<T> {
 
     pub async fn send(self) -> Result<Channel, YouTubeError> {
+        self.send_with_country_list().await.map(|(channel, _)| channel)
+    }
+
+    /// Also returns the blocked countries as `None` when the response has no microformat, where
+    /// `Channel::blocked_countries` can't tell that apart from a channel that isn't blocked anywhere.
+    pub(crate) async fn send_with_country_list(self) -> Result<(Channel, Option<Vec<String>>), YouTubeError> {
+        // The alert texts are only classified in English
+        let profile = self.client.profile_en(&self.fields);
         let request = youtube::BrowseRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { client_name: 1, client_version: "2.20240614.01.00".to_string() })
-            }),
+            context: Some(profile.context()),
             browse_id: Some(self.channel_id.clone()),
             params: Some("EgZ2aWRlb3PyBgQKAjoA".parse().unwrap()),
             continuation: None,
@@ -59,16 +94,16 @@ impl<'a> GetChannelRequest<'a> {
 
         let req = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/browse", self.ip))
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
             .header("Host", "youtubei.googleapis.com")
             .header("Content-Type", "application/x-protobuf")
             .header("X-Goog-Fieldmask", "contents.twoColumnBrowseResultsRenderer.tabs.tabRenderer.title,header.pageHeaderRenderer.content.pageHeaderViewModel(title.dynamicTextViewModel.text.attachmentRuns.element.type.imageType.image.sources.clientResource.imageName,banner.imageBannerViewModel.image.sources.url),metadata.channelMetadataRenderer(title,description,avatar.thumbnails.url,facebookProfileId),microformat.microformatDataRenderer(noindex,unlisted,familySafe,tags,availableCountries),alerts.alertRenderer.text.simpleText,header(carouselHeaderRenderer.contents.carouselItemRenderer.carouselItems.defaultPromoPanelRenderer.title.runs.text,pageHeaderRenderer)")
-            .body(Full::new(Bytes::from(payload)))
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, &profile).await?;
 
-        match resp.status() {
+        match resp.status {
             StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
             StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
             StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
@@ -76,7 +111,7 @@ impl<'a> GetChannelRequest<'a> {
             status => Err(YouTubeError::UnknownStatusCode(status)),
         }?;
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
+        let body_bytes = resp.body;
         let response = youtube::BrowseResponse::decode(body_bytes)?;
 
         // Start with a default channel struct
@@ -98,10 +133,7 @@ impl<'a> GetChannelRequest<'a> {
             has_business_email: false,
             links: Vec::new(),
             tags: Vec::new(),
-            deleted: false,
-            hidden: false,
-            terminated: false,
-            termination_reason: String::new(),
+            status: ChannelStatus::Active,
             no_index: false,
             unlisted: false,
             family_safe: true,
@@ -212,6 +244,7 @@ impl<'a> GetChannelRequest<'a> {
         }
 
         // Try to extract microformat data if available
+        let mut blocked_countries = None;
         if let Some(microformat) = &response.microformat {
             if let Some(renderer) = &microformat.microformat_data_renderer {
                 channel.no_index = renderer.noindex;
@@ -222,29 +255,23 @@ impl<'a> GetChannelRequest<'a> {
                 // Calculate blocked countries
                 let available: HashSet<_> = renderer.available_countries.iter().cloned().collect();
                 let all: HashSet<_> = ALL_COUNTRIES.iter().map(|&s| s.to_string()).collect();
-                channel.blocked_countries = all.difference(&available)
+                blocked_countries = Some(all.difference(&available)
                     .cloned()
-                    .collect::<Vec<_>>();
+                    .collect::<Vec<_>>());
             }
         }
+        channel.blocked_countries = blocked_countries.clone().unwrap_or_default();
 
         // Check for channel status from alerts if present
         if let Some(alerts) = &response.alerts {
             if let Some(alert) = &alerts.alert_renderer {
                 if let Some(text) = &alert.text {
-                    match text.simple_text.as_str() {
-                        "This channel is not available." => channel.hidden = true,
-                        "This channel does not exist." => channel.deleted = true,
-                        other => {
-                            channel.terminated = true;
-                            channel.termination_reason = other.to_string();
-                        }
-                    }
+                    channel.status = parse_channel_status(&text.simple_text);
                 }
             }
         }
 
-        Ok(channel)
+        Ok((channel, blocked_countries))
     
     }
 
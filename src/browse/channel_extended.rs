@@ -1,43 +1,38 @@
 use crate::models::*;
-use crate::{youtube, InnerTubeRequestFields, YouTubeError};
-use hyper_tls::HttpsConnector;
-use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
 use hyper::body::Bytes;
-use http_body_util::{BodyExt, Full};
 use hyper::{Method, Request, StatusCode};
 use prost::Message;
 use std::collections::HashMap;
 use once_cell::sync::Lazy;
 use scylla::frame::value::CqlTimestamp;
 use urlencoding::decode;
-use crate::utils::{parse_creation_date, parse_multiplied_string, parse_numeric_string};
 
 static COUNTRY_CODES: Lazy<HashMap<String, String>> = Lazy::new(|| {
     serde_json::from_str(include_str!("../../data/countries.json"))
         .expect("Failed to load country codes")
 });
 
-pub struct GetChannelExtendedRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+pub struct GetChannelExtendedRequest<'a, T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub channel: &'a mut Channel,
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for GetChannelExtendedRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<'a, T: Transport> AsMut<InnerTubeRequestFields> for GetChannelExtendedRequest<'a, T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
 
-impl<'a> GetChannelExtendedRequest<'a> {
+impl<'a, T: Transport> GetChannelExtendedRequest<'a, T> {
 
     pub async fn send(self) -> Result<(), YouTubeError> {
+        let locale = self.client.locale(&self.fields);
+
         let request = youtube::BrowseRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { client_name: 1, client_version: "2.20240614.01.00".to_string() })
-            }),
+            context: Some(self.client.context(&self.fields)),
             browse_id: None,
             params: None,
             continuation: Some(crate::utils::generate_continuation_token(format!("UC{}", self.channel.user_id.clone()), "8gYrGimaASYKJDY3M2UzYjY0LTAwMDAtMjRmMy04ZjMyLTU4MjQyOWM2ODNjOA%3D%3D".to_string())),
@@ -48,16 +43,16 @@ impl<'a> GetChannelExtendedRequest<'a> {
 
         let req = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/browse", self.ip))
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
             .header("Host", "youtubei.googleapis.com")
             .header("Content-Type", "application/x-protobuf")
             .header("X-Goog-Fieldmask", "onResponseReceivedEndpoints.appendContinuationItemsAction.continuationItems.aboutChannelRenderer.metadata.aboutChannelViewModel(country,subscriberCountText,viewCountText,joinedDateText.content,canonicalChannelUrl,videoCountText,signInForBusinessEmail.content,links.channelExternalLinkViewModel(title.content,link.content))")
-            .body(Full::new(Bytes::from(payload)))
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
 
-        match resp.status() {
+        match resp.status {
             StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
             StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
             StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
@@ -65,7 +60,7 @@ impl<'a> GetChannelExtendedRequest<'a> {
             status => Err(YouTubeError::UnknownStatusCode(status)),
         }?;
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
+        let body_bytes = resp.body;
         let response = youtube::BrowseResponse::decode(body_bytes)?;
 
         // Parse about channel data
@@ -75,15 +70,8 @@ impl<'a> GetChannelExtendedRequest<'a> {
                     if let Some(about_renderer) = continuation_item.about_channel_renderer {
                         if let Some(metadata) = about_renderer.metadata {
                             if let Some(view_model) = metadata.about_channel_view_model {
-                                // Parse subscriber count (e.g. "324M subscribers")
-                                if !view_model.subscriber_count_text.is_empty() {
-                                    let sub_count = view_model.subscriber_count_text
-                                        .trim_end_matches(" subscribers")
-                                        .trim();
-                                    self.channel.subscribers = Some(parse_multiplied_string(sub_count));
-                                } else {
-                                    self.channel.subscribers = Some(0);
-                                }
+                                // Parse subscriber count (e.g. "324M subscribers" or "324 Mio. Abonnenten")
+                                self.channel.subscribers = Some(locale.parse_count(&view_model.subscriber_count_text).unwrap_or(0));
 
                                 // Remove "http://www.youtube.com/" prefix
                                 if let Some(path) = view_model.canonical_channel_url.strip_prefix("http://www.youtube.com/") {
@@ -96,19 +84,13 @@ impl<'a> GetChannelExtendedRequest<'a> {
                                 }
                                 
                                 // Parse view count (e.g. "61,943,233,845 views")
-                                if !view_model.view_count_text.is_empty() {
-                                    let view_count = view_model.view_count_text
-                                        .trim_end_matches(" views")
-                                        .trim();
-                                    self.channel.views = parse_numeric_string(view_count);
+                                if let Some(views) = locale.parse_count(&view_model.view_count_text) {
+                                    self.channel.views = views;
                                 }
 
                                 // Parse video count (e.g. "823 videos")
-                                if !view_model.video_count_text.is_empty() {
-                                    let video_count = view_model.video_count_text
-                                        .trim_end_matches(" videos")
-                                        .trim();
-                                    self.channel.videos = parse_numeric_string(video_count) as i32;
+                                if let Some(videos) = locale.parse_count(&view_model.video_count_text) {
+                                    self.channel.videos = videos as i32;
                                 }
 
                                 // Parse country and convert to 2-letter code
@@ -134,10 +116,10 @@ impl<'a> GetChannelExtendedRequest<'a> {
                                     })
                                     .collect();
 
-                                // Parse join date (e.g. "Joined Feb 19, 2012")
+                                // Parse join date (e.g. "Joined Feb 19, 2012" or "Beigetreten am 19.02.2012")
                                 if let Some(joined_date) = view_model.joined_date_text {
-                                    if let Some(date_str) = joined_date.content.strip_prefix("Joined ") {
-                                        self.channel.created_at = CqlTimestamp(parse_creation_date(date_str) as i64 * 1000);
+                                    if let Some(created_at) = locale.parse_date(&joined_date.content) {
+                                        self.channel.created_at = CqlTimestamp(created_at * 1000);
                                     }
                                 }
                             }
@@ -0,0 +1,313 @@
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
+use hyper::body::Bytes;
+use hyper::{Method, Request, StatusCode};
+use prost::Message;
+use crate::models::{PlaylistStub, Product};
+use crate::locale::Locale;
+use youtube::browse_response::{LockupViewModel, VerticalProductCardRenderer};
+use youtube::browse_response::lockup_view_model::ContentType;
+
+const LOCKUP_FIELDMASK: &str = "lockupViewModel(contentId,contentType,metadata.lockupMetadataViewModel.title.content,contentImage.collectionThumbnailViewModel.primaryThumbnail.thumbnailViewModel.overlays.thumbnailOverlayBadgeViewModel.thumbnailBadges.thumbnailBadgeViewModel.text)";
+const PRODUCT_FIELDMASK: &str = "verticalProductCardRenderer(title.simpleText,price,navigationEndpoint.urlEndpoint.url)";
+const CONTINUATION_FIELDMASK: &str = "continuationItemRenderer.continuationEndpoint.continuationCommand.token";
+
+/// Channel tabs that list playlists or products instead of videos. Videos, Live and Shorts are
+/// loaded with `get_videos_extended` and `videos_stream`.
+#[derive(Debug, Clone)]
+pub enum CollectionTab {
+    Playlists,
+    Podcasts,
+    Releases,
+    Courses,
+    Store,
+}
+
+impl CollectionTab {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            CollectionTab::Playlists => "EglwbGF5bGlzdHPyBgQKAkIA",
+            CollectionTab::Podcasts => "Eghwb2RjYXN0c/IGBQoDugEA",
+            CollectionTab::Releases => "EghyZWxlYXNlc/IGBQoDsgEA",
+            CollectionTab::Courses => "Egdjb3Vyc2Vz8gYFCgPCAQA=",
+            CollectionTab::Store => "EgVzdG9yZfIGBAoCGgA=",
+        }
+    }
+}
+
+/// An item on a channel's Playlists, Podcasts, Releases, Courses or Store tab.
+#[derive(Debug, Clone)]
+pub enum ChannelTabItem {
+    Playlist(PlaylistStub),
+    Product(Product),
+}
+
+fn parse_lockup(lockup: LockupViewModel, user_id: Option<&str>, locale: Locale) -> Option<ChannelTabItem> {
+    match lockup.content_type() {
+        ContentType::LockupContentTypePlaylist | ContentType::LockupContentTypeAlbum | ContentType::LockupContentTypePodcast => {}
+        _ => return None,
+    }
+
+    if lockup.content_id.is_empty() {
+        return None;
+    }
+
+    let title = lockup.metadata
+        .and_then(|metadata| metadata.lockup_metadata_view_model)
+        .and_then(|view_model| view_model.title)
+        .map(|title| title.content)
+        .unwrap_or_default();
+
+    // Parse video count from the thumbnail badge (e.g. "12 videos", "48 episodes")
+    let video_count = lockup.content_image
+        .and_then(|image| image.collection_thumbnail_view_model)
+        .and_then(|view_model| view_model.primary_thumbnail)
+        .and_then(|thumbnail| thumbnail.thumbnail_view_model)
+        .and_then(|view_model| view_model.overlays.into_iter()
+            .filter_map(|overlay| overlay.thumbnail_overlay_badge_view_model)
+            .flat_map(|badge| badge.thumbnail_badges)
+            .filter_map(|badge| badge.thumbnail_badge_view_model)
+            .map(|badge| badge.text)
+            .find_map(|text| locale.parse_count(&text)))
+        .map(|count| count as i32);
+
+    Some(ChannelTabItem::Playlist(PlaylistStub {
+        playlist_id: lockup.content_id,
+        title,
+        user_id: user_id.map(|id| id.to_string()),
+        video_count
+    }))
+}
+
+fn parse_product(product: VerticalProductCardRenderer) -> Option<ChannelTabItem> {
+    let title = product.title.map(|title| title.simple_text).unwrap_or_default();
+    if title.is_empty() {
+        return None;
+    }
+
+    Some(ChannelTabItem::Product(Product {
+        title,
+        price: Some(product.price).filter(|price| !price.is_empty()),
+        url: product.navigation_endpoint
+            .and_then(|endpoint| endpoint.url_endpoint)
+            .map(|endpoint| endpoint.url)
+            .filter(|url| !url.is_empty())
+    }))
+}
+
+fn parse_continuation_item(item: youtube::browse_response::ContinuationItemRenderer) -> Option<String> {
+    item.continuation_endpoint
+        .and_then(|endpoint| endpoint.continuation_command)
+        .map(|command| command.token)
+        .filter(|token| !token.is_empty())
+}
+
+pub struct GetChannelTabRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub channel_id: String,
+    pub tab: CollectionTab
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetChannelTabRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> GetChannelTabRequest<T> {
+    pub async fn send(self) -> Result<(Vec<ChannelTabItem>, Option<String>), YouTubeError> {
+        let user_id = self.channel_id.strip_prefix("UC").map(|id| id.to_string());
+        let locale = self.client.locale(&self.fields);
+
+        let request = youtube::BrowseRequest {
+            context: Some(self.client.context(&self.fields)),
+            browse_id: Some(self.channel_id),
+            params: Some(self.tab.as_str().to_string()),
+            continuation: None
+        };
+
+        let mut payload = Vec::new();
+        request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
+            .header("Host", "youtubei.googleapis.com")
+            .header("Content-Type", "application/x-protobuf")
+            .header("X-Goog-Fieldmask", format!(
+                "contents.twoColumnBrowseResultsRenderer.tabs.tabRenderer.content(richGridRenderer.contents(richItemRenderer.content.{},{}),sectionListRenderer.contents.itemSectionRenderer.contents.gridRenderer.items({},{},{}))",
+                LOCKUP_FIELDMASK, CONTINUATION_FIELDMASK, LOCKUP_FIELDMASK, PRODUCT_FIELDMASK, CONTINUATION_FIELDMASK
+            ))
+            .body(Bytes::from(payload))
+            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
+
+        match resp.status {
+            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+            StatusCode::OK => Ok(()),
+            status => Err(YouTubeError::UnknownStatusCode(status)),
+        }?;
+
+        let body_bytes = resp.body;
+        let response = youtube::BrowseResponse::decode(body_bytes)?;
+
+        let mut items = Vec::new();
+        let mut continuation = None;
+
+        if let Some(contents) = response.contents {
+            if let Some(two_col) = contents.two_column_browse_results_renderer {
+                for tab in two_col.tabs {
+                    if let Some(tab_renderer) = tab.tab_renderer {
+                        if let Some(content) = tab_renderer.content {
+                            // Podcasts, Releases and Courses
+                            if let Some(grid) = content.rich_grid_renderer {
+                                for grid_content in grid.contents {
+                                    if let Some(item) = grid_content.rich_item_renderer
+                                        .and_then(|rich_item| rich_item.content)
+                                        .and_then(|content| content.lockup_view_model)
+                                        .and_then(|lockup| parse_lockup(lockup, user_id.as_deref(), locale))
+                                    {
+                                        items.push(item);
+                                    }
+
+                                    if let Some(cont_item) = grid_content.continuation_item_renderer {
+                                        if let Some(endpoint) = cont_item.continuation_endpoint {
+                                            if let Some(command) = endpoint.continuation_command {
+                                                if !command.token.is_empty() {
+                                                    continuation = Some(command.token);
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
+                            // Playlists and Store
+                            if let Some(section_list) = content.section_list_renderer {
+                                for section in section_list.contents {
+                                    if let Some(item_section) = section.item_section_renderer {
+                                        for item_content in item_section.contents {
+                                            if let Some(grid) = item_content.grid_renderer {
+                                                for grid_item in grid.items {
+                                                    if let Some(item) = grid_item.lockup_view_model.and_then(|lockup| parse_lockup(lockup, user_id.as_deref(), locale)) {
+                                                        items.push(item);
+                                                    }
+
+                                                    if let Some(item) = grid_item.vertical_product_card_renderer.and_then(parse_product) {
+                                                        items.push(item);
+                                                    }
+
+                                                    if let Some(token) = grid_item.continuation_item_renderer.and_then(parse_continuation_item) {
+                                                        continuation = Some(token);
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok((items, continuation))
+    }
+}
+
+pub struct GetChannelTabContinuationRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub continuation_token: String,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetChannelTabContinuationRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> GetChannelTabContinuationRequest<T> {
+    pub async fn send(self) -> Result<(Vec<ChannelTabItem>, Option<String>), YouTubeError> {
+        let locale = self.client.locale(&self.fields);
+
+        let request = youtube::BrowseRequest {
+            context: Some(self.client.context(&self.fields)),
+            browse_id: None,
+            params: None,
+            continuation: Some(self.continuation_token),
+        };
+
+        let mut payload = Vec::new();
+        request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
+            .header("Host", "youtubei.googleapis.com")
+            .header("Content-Type", "application/x-protobuf")
+            .header("X-Goog-Fieldmask", format!(
+                "onResponseReceivedActions.appendContinuationItemsAction.continuationItems(richItemRenderer.content.{},{},{},{})",
+                LOCKUP_FIELDMASK, LOCKUP_FIELDMASK, PRODUCT_FIELDMASK, CONTINUATION_FIELDMASK
+            ))
+            .body(Bytes::from(payload))
+            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
+
+        match resp.status {
+            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+            StatusCode::OK => Ok(()),
+            status => Err(YouTubeError::UnknownStatusCode(status)),
+        }?;
+
+        let body_bytes = resp.body;
+        let response = youtube::BrowseResponse::decode(body_bytes)?;
+
+        let mut items = Vec::new();
+        let mut continuation = None;
+
+        if let Some(actions) = response.on_response_received_actions {
+            if let Some(action) = actions.append_continuation_items_action {
+                for item in action.continuation_items {
+                    // The owner isn't part of the continuation
+                    if let Some(parsed) = item.rich_item_renderer
+                        .and_then(|rich_item| rich_item.content)
+                        .and_then(|content| content.lockup_view_model)
+                        .and_then(|lockup| parse_lockup(lockup, None, locale))
+                    {
+                        items.push(parsed);
+                    }
+
+                    if let Some(parsed) = item.lockup_view_model.and_then(|lockup| parse_lockup(lockup, None, locale)) {
+                        items.push(parsed);
+                    }
+
+                    if let Some(parsed) = item.vertical_product_card_renderer.and_then(parse_product) {
+                        items.push(parsed);
+                    }
+
+                    // Extract continuation token if present
+                    if let Some(cont_item) = item.continuation_item_renderer {
+                        if let Some(endpoint) = cont_item.continuation_endpoint {
+                            if let Some(command) = endpoint.continuation_command {
+                                if !command.token.is_empty() {
+                                    continuation = Some(command.token);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok((items, continuation))
+    }
+}
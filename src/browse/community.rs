@@ -0,0 +1,259 @@
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
+use hyper::body::Bytes;
+use hyper::{Method, Request, StatusCode};
+use prost::Message;
+use crate::models::{CommunityPost, PostAttachment};
+use crate::locale::Locale;
+use youtube::browse_response::BackstagePostThreadRenderer;
+use youtube::browse_response::backstage_post_thread_renderer::post::backstage_post_renderer::{BackstageImageRenderer, Text};
+
+const COMMUNITY_TAB_PARAMS: &str = "Egljb21tdW5pdHnyBgQKAkoA";
+const POST_FIELDMASK: &str = "backstagePostThreadRenderer.post.backstagePostRenderer(postId,authorEndpoint.browseEndpoint.browseId,contentText.runs.text,backstageAttachment(videoRenderer.videoId,pollRenderer(choices.text.runs.text,totalVotes.simpleText),backstageImageRenderer.image.thumbnails.url,postMultiImageRenderer.images.backstageImageRenderer.image.thumbnails.url),publishedTimeText.runs.text,voteCount.simpleText)";
+const CONTINUATION_FIELDMASK: &str = "continuationItemRenderer.continuationEndpoint.continuationCommand.token";
+
+fn runs_text(text: Option<Text>) -> String {
+    text.map(|text| text.runs.into_iter().map(|run| run.text).collect())
+        .unwrap_or_default()
+}
+
+// Thumbnails are ordered smallest to largest
+fn largest_image(image: BackstageImageRenderer) -> Option<String> {
+    image.image
+        .and_then(|image| image.thumbnails.into_iter().last())
+        .map(|thumbnail| thumbnail.url)
+}
+
+fn parse_post(thread: BackstagePostThreadRenderer, locale: Locale) -> Option<CommunityPost> {
+    let post = thread.post.and_then(|post| post.backstage_post_renderer)?;
+    if post.post_id.is_empty() {
+        return None;
+    }
+
+    let user_id = post.author_endpoint
+        .and_then(|endpoint| endpoint.browse_endpoint)
+        .and_then(|browse| browse.browse_id.strip_prefix("UC").map(|id| id.to_string()))
+        .unwrap_or_default();
+
+    let attachment = post.backstage_attachment.and_then(|attachment| {
+        if let Some(video) = attachment.video_renderer {
+            if !video.video_id.is_empty() {
+                return Some(PostAttachment::Video(video.video_id));
+            }
+        }
+
+        if let Some(poll) = attachment.poll_renderer {
+            // Parse vote count (e.g. "12K votes")
+            let total_votes = poll.total_votes
+                .and_then(|votes| locale.parse_count(&votes.simple_text))
+                .unwrap_or_default();
+
+            return Some(PostAttachment::Poll {
+                choices: poll.choices.into_iter().map(|choice| runs_text(choice.text)).collect(),
+                total_votes
+            });
+        }
+
+        if let Some(image) = attachment.backstage_image_renderer {
+            return largest_image(image).map(|url| PostAttachment::Images(vec![url]));
+        }
+
+        if let Some(multi_image) = attachment.post_multi_image_renderer {
+            let images: Vec<String> = multi_image.images
+                .into_iter()
+                .filter_map(|image| image.backstage_image_renderer.and_then(largest_image))
+                .collect();
+            if !images.is_empty() {
+                return Some(PostAttachment::Images(images));
+            }
+        }
+
+        None
+    });
+
+    let (created_at, edited) = locale.parse_relative_time_edited(&runs_text(post.published_time_text));
+
+    // No vote count is returned when the post has no likes
+    let likes = post.vote_count
+        .and_then(|count| locale.parse_count(&count.simple_text))
+        .unwrap_or(0) as i32;
+
+    Some(CommunityPost {
+        post_id: post.post_id,
+        user_id,
+        content_text: runs_text(post.content_text),
+        attachment,
+        likes,
+        created_at: created_at.unwrap_or_default(),
+        edited
+    })
+}
+
+pub struct GetCommunityPostsRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub channel_id: String,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetCommunityPostsRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> GetCommunityPostsRequest<T> {
+    pub async fn send(self) -> Result<(Vec<CommunityPost>, Option<String>), YouTubeError> {
+        let locale = self.client.locale(&self.fields);
+
+        let request = youtube::BrowseRequest {
+            context: Some(self.client.context(&self.fields)),
+            browse_id: Some(self.channel_id),
+            params: Some(COMMUNITY_TAB_PARAMS.to_string()),
+            continuation: None
+        };
+
+        let mut payload = Vec::new();
+        request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
+            .header("Host", "youtubei.googleapis.com")
+            .header("Content-Type", "application/x-protobuf")
+            .header("X-Goog-Fieldmask", format!(
+                "contents.twoColumnBrowseResultsRenderer.tabs.tabRenderer.content.sectionListRenderer.contents.itemSectionRenderer.contents({},{})",
+                POST_FIELDMASK, CONTINUATION_FIELDMASK
+            ))
+            .body(Bytes::from(payload))
+            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
+
+        match resp.status {
+            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+            StatusCode::OK => Ok(()),
+            status => Err(YouTubeError::UnknownStatusCode(status)),
+        }?;
+
+        let body_bytes = resp.body;
+        let response = youtube::BrowseResponse::decode(body_bytes)?;
+
+        let mut posts = Vec::new();
+        let mut continuation = None;
+
+        if let Some(contents) = response.contents {
+            if let Some(two_col) = contents.two_column_browse_results_renderer {
+                for tab in two_col.tabs {
+                    if let Some(tab_renderer) = tab.tab_renderer {
+                        if let Some(content) = tab_renderer.content {
+                            if let Some(section_list) = content.section_list_renderer {
+                                for section in section_list.contents {
+                                    if let Some(item_section) = section.item_section_renderer {
+                                        for item in item_section.contents {
+                                            if let Some(post) = item.backstage_post_thread_renderer.and_then(|post| parse_post(post, locale)) {
+                                                posts.push(post);
+                                            }
+
+                                            if let Some(cont_item) = item.continuation_item_renderer {
+                                                if let Some(endpoint) = cont_item.continuation_endpoint {
+                                                    if let Some(command) = endpoint.continuation_command {
+                                                        if !command.token.is_empty() {
+                                                            continuation = Some(command.token);
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok((posts, continuation))
+    }
+}
+
+pub struct GetCommunityPostsContinuationRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub continuation_token: String,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetCommunityPostsContinuationRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> GetCommunityPostsContinuationRequest<T> {
+    pub async fn send(self) -> Result<(Vec<CommunityPost>, Option<String>), YouTubeError> {
+        let locale = self.client.locale(&self.fields);
+
+        let request = youtube::BrowseRequest {
+            context: Some(self.client.context(&self.fields)),
+            browse_id: None,
+            params: None,
+            continuation: Some(self.continuation_token),
+        };
+
+        let mut payload = Vec::new();
+        request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
+            .header("Host", "youtubei.googleapis.com")
+            .header("Content-Type", "application/x-protobuf")
+            .header("X-Goog-Fieldmask", format!(
+                "onResponseReceivedEndpoints.appendContinuationItemsAction.continuationItems({},{})",
+                POST_FIELDMASK, CONTINUATION_FIELDMASK
+            ))
+            .body(Bytes::from(payload))
+            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
+
+        match resp.status {
+            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+            StatusCode::OK => Ok(()),
+            status => Err(YouTubeError::UnknownStatusCode(status)),
+        }?;
+
+        let body_bytes = resp.body;
+        let response = youtube::BrowseResponse::decode(body_bytes)?;
+
+        let mut posts = Vec::new();
+        let mut continuation = None;
+
+        if let Some(endpoints) = response.on_response_received_endpoints {
+            if let Some(action) = endpoints.append_continuation_items_action {
+                for item in action.continuation_items {
+                    if let Some(post) = item.backstage_post_thread_renderer.and_then(|post| parse_post(post, locale)) {
+                        posts.push(post);
+                    }
+
+                    // Extract continuation token if present
+                    if let Some(cont_item) = item.continuation_item_renderer {
+                        if let Some(endpoint) = cont_item.continuation_endpoint {
+                            if let Some(command) = endpoint.continuation_command {
+                                if !command.token.is_empty() {
+                                    continuation = Some(command.token);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok((posts, continuation))
+    }
+}
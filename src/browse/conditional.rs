@@ -1,5 +1,8 @@
-use crate::{youtube, YouTubeError};
-use reqwest;
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::proxy::Proxy;
+use crate::transport::{HyperTransport, Transport};
+use hyper::body::Bytes;
+use hyper::{Method, Request, StatusCode};
 use prost::Message;
 
 #[derive(Debug, Clone)]
@@ -13,20 +16,28 @@ pub struct CountryCodeResult {
     pub country_code: String,
 }
 
-pub struct ResolveConditionalRedirectRequest {
+/// Browses a channel from the country of `proxy_url`, conditional redirects depend on where the request comes from.
+pub struct ResolveConditionalRedirectRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub proxy_url: String,
     pub channel_id: String,
 }
 
-impl ResolveConditionalRedirectRequest {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for ResolveConditionalRedirectRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> ResolveConditionalRedirectRequest<T> {
     pub async fn send(self) -> Result<Option<ConditionalRedirectResult>, YouTubeError> {
+        let proxy = Proxy::parse(&self.proxy_url)?;
+
+        // The blocked alert is only recognized in English
+        let profile = self.client.profile_en(&self.fields);
         let request = youtube::BrowseRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { 
-                    client_name: 1, 
-                    client_version: "2.20240614.01.00".to_string() 
-                })
-            }),
+            context: Some(profile.context()),
             browse_id: Some(self.channel_id.clone()),
             params: None,
             continuation: None,
@@ -35,37 +46,27 @@ impl ResolveConditionalRedirectRequest {
         let mut payload = Vec::new();
         request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let proxy = reqwest::Proxy::all(&self.proxy_url)
-            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
-
-        let client = reqwest::Client::builder()
-            .proxy(proxy)
-            .build()
-            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
-
-        let response = client
-            .post("https://youtubei.googleapis.com/youtubei/v1/browse")
+        let mut req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
             .header("Host", "youtubei.googleapis.com")
-            .header("X-Goog-Fieldmask", "alerts,onResponseReceivedActions.navigateAction.endpoint.browseEndpoint")
             .header("Content-Type", "application/x-protobuf")
-            .body(payload)
-            .send()
-            .await
+            .header("X-Goog-Fieldmask", "alerts,onResponseReceivedActions.navigateAction.endpoint.browseEndpoint")
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+        req.extensions_mut().insert(proxy);
 
-        match response.status() {
-            reqwest::StatusCode::NOT_FOUND => return Err(YouTubeError::NotFound),
-            reqwest::StatusCode::TOO_MANY_REQUESTS => return Err(YouTubeError::Ratelimited),
-            reqwest::StatusCode::UNAUTHORIZED => return Err(YouTubeError::Unauthorized),
-            reqwest::StatusCode::OK => {},
-            status => return Err(YouTubeError::UnknownStatusCode(hyper::StatusCode::from_u16(status.as_u16()).unwrap())),
-        }
+        let resp = self.client.send(req, &profile).await?;
 
-        let body_bytes = response.bytes().await
-            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
-        
-        let browse_response = youtube::BrowseResponse::decode(body_bytes)
-            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+        match resp.status {
+            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+            StatusCode::OK => Ok(()),
+            status => Err(YouTubeError::UnknownStatusCode(status)),
+        }?;
+
+        let browse_response = youtube::BrowseResponse::decode(resp.body)?;
 
         // Check for blocked channel in alerts
         if let Some(alerts) = &browse_response.alerts {
@@ -94,19 +95,25 @@ impl ResolveConditionalRedirectRequest {
     }
 }
 
-pub struct DetectCountryCodeRequest {
+/// Detects which country YouTube places requests made through `proxy_url` in.
+pub struct DetectCountryCodeRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub proxy_url: String,
 }
 
-impl DetectCountryCodeRequest {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for DetectCountryCodeRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> DetectCountryCodeRequest<T> {
     pub async fn send(self) -> Result<CountryCodeResult, YouTubeError> {
+        let proxy = Proxy::parse(&self.proxy_url)?;
+
         let request = youtube::BrowseRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { 
-                    client_name: 1, 
-                    client_version: "2.20240614.01.00".to_string() 
-                })
-            }),
+            context: Some(self.client.context(&self.fields)),
             browse_id: Some("FEwhat_to_watch".to_string()),
             params: None,
             continuation: None,
@@ -115,37 +122,27 @@ impl DetectCountryCodeRequest {
         let mut payload = Vec::new();
         request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let proxy = reqwest::Proxy::all(&self.proxy_url)
-            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
-
-        let client = reqwest::Client::builder()
-            .proxy(proxy)
-            .build()
-            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
-
-        let response = client
-            .post("https://youtubei.googleapis.com/youtubei/v1/browse")
+        let mut req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
             .header("Host", "youtubei.googleapis.com")
-            .header("X-Goog-Fieldmask", "topbar.desktopTopbarRenderer.countryCode")
             .header("Content-Type", "application/x-protobuf")
-            .body(payload)
-            .send()
-            .await
+            .header("X-Goog-Fieldmask", "topbar.desktopTopbarRenderer.countryCode")
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+        req.extensions_mut().insert(proxy);
 
-        match response.status() {
-            reqwest::StatusCode::NOT_FOUND => return Err(YouTubeError::NotFound),
-            reqwest::StatusCode::TOO_MANY_REQUESTS => return Err(YouTubeError::Ratelimited),
-            reqwest::StatusCode::UNAUTHORIZED => return Err(YouTubeError::Unauthorized),
-            reqwest::StatusCode::OK => {},
-            status => return Err(YouTubeError::UnknownStatusCode(hyper::StatusCode::from_u16(status.as_u16()).unwrap())),
-        }
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
 
-        let body_bytes = response.bytes().await
-            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
-        
-        let browse_response = youtube::BrowseResponse::decode(body_bytes)
-            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+        match resp.status {
+            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+            StatusCode::OK => Ok(()),
+            status => Err(YouTubeError::UnknownStatusCode(status)),
+        }?;
+
+        let browse_response = youtube::BrowseResponse::decode(resp.body)?;
 
         // Extract country code from topbar
         if let Some(topbar) = &browse_response.topbar {
@@ -2,5 +2,10 @@ pub mod channel;
 pub mod channel_extended;
 pub mod videos;
 pub mod videos_continued;
+pub mod videos_stream;
 pub mod subscriptions;
-pub mod conditional;
\ No newline at end of file
+pub mod conditional;
+pub mod playlist;
+pub mod playlist_continued;
+pub mod channel_tab;
+pub mod community;
\ No newline at end of file
@@ -0,0 +1,215 @@
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
+use hyper::body::Bytes;
+use hyper::{Method, Request, StatusCode};
+use prost::Message;
+use crate::models::{Playlist, PlaylistEntry, PlaylistVisibility, PublishedTimePrecision, Video};
+use crate::locale::Locale;
+use crate::browse::videos::parse_length_text;
+use youtube::browse_response::header::playlist_header_renderer::{Privacy, Text};
+
+pub(crate) const PLAYLIST_VIDEO_FIELDMASK: &str = "playlistVideoRenderer(videoId,index.simpleText,lengthText.simpleText,videoInfo.runs.text,upcomingEventData)";
+
+pub struct GetPlaylistResponse {
+    pub playlist: Playlist,
+    pub entries: Vec<PlaylistEntry>,
+    pub continuation: Option<String>
+}
+
+fn runs_text(text: &Text) -> String {
+    text.runs.iter().map(|run| run.text.as_str()).collect()
+}
+
+// e.g. "Last updated on Jan 5, 2024", "Updated today" or "Updated 3 days ago"
+fn parse_last_updated_text(text: &str, locale: Locale) -> Option<i64> {
+    locale.parse_date(text).or_else(|| locale.parse_relative_time(text))
+}
+
+pub(crate) fn parse_playlist_video(video: youtube::browse_response::PlaylistVideoRenderer, locale: Locale) -> Option<PlaylistEntry> {
+    // Skip upcoming premieres and unavailable entries
+    if video.upcoming_event_data.is_some() || video.video_id.is_empty() {
+        return None;
+    }
+
+    let index = video.index
+        .and_then(|index| locale.parse_count(&index.simple_text))
+        .map(|index| index as i32)
+        .unwrap_or_default();
+
+    let info: Vec<String> = video.video_info
+        .map(|info| info.runs.into_iter()
+            .map(|run| run.text)
+            .filter(|text| text.trim() != "•")
+            .collect())
+        .unwrap_or_default();
+
+    let (approx_published_time, published_time_precision) = info.iter()
+        .find_map(|text| locale.parse_relative_time_with_unit(text))
+        .map(|(timestamp, unit)| (Some(timestamp), Some(PublishedTimePrecision::Approximate(unit))))
+        .unwrap_or_default();
+
+    // The view count is left out of videoInfo when it is hidden, leaving only the publish time
+    let (views, hidden_view_count) = match info.iter().find(|text| locale.parse_relative_time(text).is_none()) {
+        Some(text) => (locale.parse_count(text).unwrap_or_default(), false),
+        None => (0, true),
+    };
+
+    let length_seconds = video.length_text
+        .as_ref()
+        .and_then(|lt| parse_length_text(&lt.simple_text));
+
+    Some(PlaylistEntry {
+        index,
+        video: Video {
+            video_id: video.video_id,
+            views,
+            hidden_view_count,
+            badge: None,
+            length_seconds,
+            approx_published_time,
+            published_time_precision
+        }
+    })
+}
+
+pub struct GetPlaylistRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub playlist_id: String,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetPlaylistRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> GetPlaylistRequest<T> {
+    pub async fn send(self) -> Result<GetPlaylistResponse, YouTubeError> {
+        let locale = self.client.locale(&self.fields);
+
+        // Accept both "PL..." and "VLPL..."
+        let playlist_id = self.playlist_id.strip_prefix("VL").unwrap_or(&self.playlist_id).to_string();
+
+        let request = youtube::BrowseRequest {
+            context: Some(self.client.context(&self.fields)),
+            browse_id: Some(format!("VL{}", playlist_id)),
+            params: None,
+            continuation: None,
+        };
+
+        let mut payload = Vec::new();
+        request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
+            .header("Host", "youtubei.googleapis.com")
+            .header("Content-Type", "application/x-protobuf")
+            .header("X-Goog-Fieldmask", format!("header.playlistHeaderRenderer(playlistId,title.simpleText,numVideosText.runs.text,ownerText.runs(text,navigationEndpoint.browseEndpoint.browseId),viewCountText.simpleText,privacy,stats.runs.text),contents.twoColumnBrowseResultsRenderer.tabs.tabRenderer.content.sectionListRenderer.contents.itemSectionRenderer.contents.playlistVideoListRenderer.contents({},continuationItemRenderer.continuationEndpoint.continuationCommand.token)", PLAYLIST_VIDEO_FIELDMASK))
+            .body(Bytes::from(payload))
+            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
+
+        match resp.status {
+            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+            StatusCode::OK => Ok(()),
+            status => Err(YouTubeError::UnknownStatusCode(status)),
+        }?;
+
+        let body_bytes = resp.body;
+        let response = youtube::BrowseResponse::decode(body_bytes)?;
+
+        // Private and deleted playlists come back without a header
+        let header = response.header
+            .and_then(|header| header.playlist_header_renderer)
+            .ok_or(YouTubeError::NotFound)?;
+
+        let user_id = header.owner_text
+            .as_ref()
+            .and_then(|owner| owner.runs.iter().find_map(|run| run.navigation_endpoint.as_ref()))
+            .and_then(|endpoint| endpoint.browse_endpoint.as_ref())
+            .and_then(|browse| browse.browse_id.strip_prefix("UC"))
+            .map(|id| id.to_string());
+
+        // Parse video count (e.g. "1,204 videos")
+        let video_count = header.num_videos_text
+            .as_ref()
+            .and_then(|text| locale.parse_count(&runs_text(text)))
+            .unwrap_or_default() as i32;
+
+        // Parse view count (e.g. "61,943 views"), absent on playlists without views
+        let views = header.view_count_text
+            .as_ref()
+            .and_then(|text| locale.parse_count(&text.simple_text))
+            .unwrap_or_default();
+
+        let last_updated = header.stats
+            .iter()
+            .find_map(|stat| parse_last_updated_text(&runs_text(stat), locale));
+
+        let visibility = match header.privacy() {
+            Privacy::Unlisted => PlaylistVisibility::Unlisted,
+            Privacy::Private => PlaylistVisibility::Private,
+            _ => PlaylistVisibility::Public,
+        };
+
+        let playlist = Playlist {
+            playlist_id: if header.playlist_id.is_empty() { playlist_id } else { header.playlist_id },
+            title: header.title.map(|title| title.simple_text).unwrap_or_default(),
+            user_id,
+            video_count,
+            views,
+            last_updated,
+            visibility
+        };
+
+        let mut entries = Vec::new();
+        let mut continuation = None;
+
+        if let Some(contents) = response.contents {
+            if let Some(renderer) = contents.two_column_browse_results_renderer {
+                for tab in renderer.tabs {
+                    if let Some(tab_renderer) = tab.tab_renderer {
+                        if let Some(content) = tab_renderer.content {
+                            if let Some(section_list) = content.section_list_renderer {
+                                for section in section_list.contents {
+                                    if let Some(item_section) = section.item_section_renderer {
+                                        for item in item_section.contents {
+                                            if let Some(list) = item.playlist_video_list_renderer {
+                                                for list_item in list.contents {
+                                                    if let Some(entry) = list_item.playlist_video_renderer.and_then(|video| parse_playlist_video(video, locale)) {
+                                                        entries.push(entry);
+                                                    }
+
+                                                    if let Some(cont_item) = list_item.continuation_item_renderer {
+                                                        if let Some(endpoint) = cont_item.continuation_endpoint {
+                                                            if let Some(command) = endpoint.continuation_command {
+                                                                if !command.token.is_empty() {
+                                                                    continuation = Some(command.token);
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(GetPlaylistResponse {
+            playlist,
+            entries,
+            continuation
+        })
+    }
+}
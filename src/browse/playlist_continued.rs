@@ -0,0 +1,83 @@
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
+use hyper::body::Bytes;
+use hyper::{Method, Request, StatusCode};
+use prost::Message;
+use crate::models::PlaylistEntry;
+use crate::browse::playlist::{parse_playlist_video, PLAYLIST_VIDEO_FIELDMASK};
+
+pub struct GetPlaylistContinuationRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub continuation_token: String,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetPlaylistContinuationRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> GetPlaylistContinuationRequest<T> {
+    pub async fn send(self) -> Result<(Vec<PlaylistEntry>, Option<String>), YouTubeError> {
+        let locale = self.client.locale(&self.fields);
+
+        let request = youtube::BrowseRequest {
+            context: Some(self.client.context(&self.fields)),
+            browse_id: None,
+            params: None,
+            continuation: Some(self.continuation_token),
+        };
+
+        let mut payload = Vec::new();
+        request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
+            .header("Host", "youtubei.googleapis.com")
+            .header("Content-Type", "application/x-protobuf")
+            .header("X-Goog-Fieldmask", format!("onResponseReceivedActions.appendContinuationItemsAction.continuationItems({},continuationItemRenderer.continuationEndpoint.continuationCommand.token)", PLAYLIST_VIDEO_FIELDMASK))
+            .body(Bytes::from(payload))
+            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
+
+        match resp.status {
+            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+            StatusCode::OK => Ok(()),
+            status => Err(YouTubeError::UnknownStatusCode(status)),
+        }?;
+
+        let body_bytes = resp.body;
+        let response = youtube::BrowseResponse::decode(body_bytes)?;
+
+        let mut entries = Vec::new();
+        let mut continuation = None;
+
+        if let Some(actions) = response.on_response_received_actions {
+            if let Some(action) = actions.append_continuation_items_action {
+                for item in action.continuation_items {
+                    if let Some(entry) = item.playlist_video_renderer.and_then(|video| parse_playlist_video(video, locale)) {
+                        entries.push(entry);
+                    }
+
+                    // Extract continuation token if present
+                    if let Some(cont_item) = item.continuation_item_renderer {
+                        if let Some(endpoint) = cont_item.continuation_endpoint {
+                            if let Some(command) = endpoint.continuation_command {
+                                if !command.token.is_empty() {
+                                    continuation = Some(command.token);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok((entries, continuation))
+    }
+}
@@ -1,34 +1,28 @@
-use crate::{youtube, InnerTubeRequestFields, YouTubeError};
-use hyper_tls::HttpsConnector;
-use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::client_profile::ClientName;
+use crate::transport::{HyperTransport, Transport};
 use hyper::body::Bytes;
-use http_body_util::{BodyExt, Full};
 use hyper::{Method, Request, StatusCode};
 use prost::Message;
 
-pub struct HasPublicSubscriptionsRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+pub struct HasPublicSubscriptionsRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub channel_id: String,
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for HasPublicSubscriptionsRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for HasPublicSubscriptionsRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
 
-impl<'a> HasPublicSubscriptionsRequest<'a> {
+impl<T: Transport> HasPublicSubscriptionsRequest<T> {
     pub async fn send(self) -> Result<bool, YouTubeError> {
+        let profile = self.client.profile_as(&self.fields, ClientName::Tvhtml5);
+
         let request = youtube::BrowseRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { 
-                    client_name: 7, // TVHTML5
-                    client_version: "7.20250126.17.00".to_string()
-                })
-            }),
+            context: Some(profile.context()),
             browse_id: Some(self.channel_id),
             params: None,
             continuation: None,
@@ -39,16 +33,16 @@ impl<'a> HasPublicSubscriptionsRequest<'a> {
 
         let req = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/browse", self.ip))
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
             .header("Host", "youtubei.googleapis.com")
             .header("Content-Type", "application/x-protobuf")
             .header("X-Goog-Fieldmask", "contents.tvBrowseRenderer.content.tvSurfaceContentRenderer.content.sectionListRenderer.contents.shelfRenderer.headerRenderer.shelfHeaderRenderer.avatarLockup.avatarLockupRenderer.title.simpleText")
-            .body(Full::new(Bytes::from(payload)))
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, &profile).await?;
 
-        match resp.status() {
+        match resp.status {
             StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
             StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
             StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
@@ -56,7 +50,7 @@ impl<'a> HasPublicSubscriptionsRequest<'a> {
             status => Err(YouTubeError::UnknownStatusCode(status)),
         }?;
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
+        let body_bytes = resp.body;
         let response = youtube::BrowseResponse::decode(body_bytes)?;
 
         if let Some(contents) = response.contents {
@@ -1,16 +1,13 @@
-use crate::{youtube, InnerTubeRequestFields, YouTubeError};
-use hyper_tls::HttpsConnector;
-use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
 use hyper::body::Bytes;
-use http_body_util::{BodyExt, Full};
 use hyper::{Method, Request, StatusCode};
 use prost::Message;
-use crate::models::Video;
-use crate::utils::{generate_continuation_token, parse_numeric_string};
+use crate::models::{PublishedTimePrecision, Video};
+use crate::utils::generate_continuation_token;
 use std::collections::HashSet;
 use once_cell::sync::Lazy;
-use std::time::{SystemTime, UNIX_EPOCH};
+use crate::locale::Locale;
 
 pub static BLACKLISTED_BADGE_LABELS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     HashSet::from([
@@ -39,70 +36,83 @@ pub fn parse_length_text(length_text: &str) -> Option<i32> {
     }
 }
 
+// English only, requests parse with the locale of their client profile
 pub fn parse_published_time_text(published_text: &str) -> Option<i64> {
-    let current_time = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .ok()?
-        .as_secs() as i64;
-    
-    let text = published_text.to_lowercase();
-    
-    if text.contains("second") {
-        if let Some(num_str) = text.split_whitespace().next() {
-            if let Ok(seconds) = num_str.parse::<i64>() {
-                return Some(current_time - seconds);
-            }
-        }
-    } else if text.contains("minute") {
-        if let Some(num_str) = text.split_whitespace().next() {
-            if let Ok(minutes) = num_str.parse::<i64>() {
-                return Some(current_time - (minutes * 60));
-            }
-        }
-    } else if text.contains("hour") {
-        if let Some(num_str) = text.split_whitespace().next() {
-            if let Ok(hours) = num_str.parse::<i64>() {
-                return Some(current_time - (hours * 3600));
-            }
-        }
-    } else if text.contains("day") {
-        if let Some(num_str) = text.split_whitespace().next() {
-            if let Ok(days) = num_str.parse::<i64>() {
-                return Some(current_time - (days * 86400));
-            }
-        }
-    } else if text.contains("week") {
-        if let Some(num_str) = text.split_whitespace().next() {
-            if let Ok(weeks) = num_str.parse::<i64>() {
-                return Some(current_time - (weeks * 604800));
-            }
-        }
-    } else if text.contains("month") {
-        if let Some(num_str) = text.split_whitespace().next() {
-            if let Ok(months) = num_str.parse::<i64>() {
-                return Some(current_time - (months * 2629746)); // ~30.44 days per month
-            }
-        }
-    } else if text.contains("year") {
-        if let Some(num_str) = text.split_whitespace().next() {
-            if let Ok(years) = num_str.parse::<i64>() {
-                return Some(current_time - (years * 31556952)); // ~365.25 days per year
-            }
-        }
+    Locale::En.parse_relative_time(published_text)
+}
+
+// e.g. "3 weeks ago", which is only accurate to about a week
+pub(crate) fn parse_approx_published_time(published_text: Option<&str>, locale: Locale) -> (Option<i64>, Option<PublishedTimePrecision>) {
+    match published_text.and_then(|text| locale.parse_relative_time_with_unit(text)) {
+        Some((timestamp, unit)) => (Some(timestamp), Some(PublishedTimePrecision::Approximate(unit))),
+        None => (None, None),
     }
-    
-    None
 }
 
-pub struct GetVideosRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+// e.g. "1,234 views", "1.2M views" or "No views". Missing when the owner hides it.
+pub(crate) fn parse_view_count_text(view_text: Option<&str>, locale: Locale) -> (i64, bool) {
+    match view_text.map(str::trim) {
+        Some(view_text) if !view_text.is_empty() => (locale.parse_count(view_text).unwrap_or_default(), false),
+        _ => (0, true),
+    }
+}
+
+pub(crate) fn parse_shorts_lockup(lockup: youtube::browse_response::ShortsLockupViewModel, locale: Locale) -> Option<Video> {
+    let video_id = lockup.on_tap
+        .and_then(|on_tap| on_tap.innertube_command)
+        .and_then(|command| command.reel_watch_endpoint)
+        .map(|endpoint| endpoint.video_id)
+        .filter(|video_id| !video_id.is_empty())
+        .or_else(|| lockup.entity_id.strip_prefix("shorts-shelf-item-").map(|id| id.to_string()))?;
+
+    let (views, hidden_view_count) = parse_view_count_text(
+        lockup.overlay_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.secondary_text.as_ref())
+            .map(|text| text.content.as_str()),
+        locale
+    );
+
+    Some(Video {
+        video_id,
+        views,
+        hidden_view_count,
+        badge: None,
+        length_seconds: None,
+        approx_published_time: None,
+        published_time_precision: None
+    })
+}
+
+pub(crate) fn parse_reel_item(reel: youtube::browse_response::ReelItemRenderer, locale: Locale) -> Option<Video> {
+    if reel.video_id.is_empty() {
+        return None;
+    }
+
+    let (views, hidden_view_count) = parse_view_count_text(
+        reel.view_count_text.as_ref().map(|text| text.simple_text.as_str()),
+        locale
+    );
+
+    Some(Video {
+        video_id: reel.video_id,
+        views,
+        hidden_view_count,
+        badge: None,
+        length_seconds: None,
+        approx_published_time: None,
+        published_time_precision: None
+    })
+}
+
+pub struct GetVideosRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub channel_id: String,
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for GetVideosRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetVideosRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
@@ -112,13 +122,11 @@ pub struct GetVideosResponse {
     pub continuation: Option<String>
 }
 
-impl<'a> GetVideosRequest<'a> {
+impl<T: Transport> GetVideosRequest<T> {
 
     pub async fn send(self) -> Result<GetVideosResponse, YouTubeError> {
         let request = youtube::BrowseRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { client_name: 1, client_version: "2.20240614.01.00".to_string() })
-            }),
+            context: Some(self.client.context(&self.fields)),
             browse_id: Some(self.channel_id),
             params: Some("EgZ2aWRlb3PyBgQKAjoA".to_string()),
             continuation: None
@@ -129,16 +137,16 @@ impl<'a> GetVideosRequest<'a> {
 
         let req = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/browse", self.ip))
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
             .header("Host", "youtubei.googleapis.com")
             .header("Content-Type", "application/x-protobuf")
             .header("X-Goog-Fieldmask", "contents.twoColumnBrowseResultsRenderer.tabs.tabRenderer.content.richGridRenderer.contents(richItemRenderer.content.videoRenderer.videoId,continuationItemRenderer.continuationEndpoint.continuationCommand.token)")
-            .body(Full::new(Bytes::from(payload)))
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
 
-        match resp.status() {
+        match resp.status {
             StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
             StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
             StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
@@ -146,7 +154,7 @@ impl<'a> GetVideosRequest<'a> {
             status => Err(YouTubeError::UnknownStatusCode(status)),
         }?;
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
+        let body_bytes = resp.body;
         let response = youtube::BrowseResponse::decode(body_bytes)?;
 
         // Parse about videos data
@@ -203,41 +211,39 @@ impl<'a> GetVideosRequest<'a> {
 pub enum ChannelTab {
     Videos,
     Live,
+    Shorts,
 }
 
 impl ChannelTab {
-    fn as_str(&self) -> &'static str {
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
             ChannelTab::Videos => "EgZ2aWRlb3PyBgQKAjoA",
             ChannelTab::Live => "EgdzdHJlYW1z8gYECgJ6AA==",
+            ChannelTab::Shorts => "EgZzaG9ydHPyBgUKA5oBAA==",
         }
     }
 }
 
-pub struct GetVideosExtendedRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+pub struct GetVideosExtendedRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub channel_id: String,
     pub tab: ChannelTab
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for GetVideosExtendedRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetVideosExtendedRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
 
-impl<'a> GetVideosExtendedRequest<'a> {
+impl<T: Transport> GetVideosExtendedRequest<T> {
 
     pub async fn send(self) -> Result<(Vec<Video>, Option<String>), YouTubeError> {
+        let locale = self.client.locale(&self.fields);
+
         let request = youtube::BrowseRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { 
-                    client_name: 1, 
-                    client_version: "2.20240614.01.00".to_string() 
-                })
-            }),
+            context: Some(self.client.context(&self.fields)),
             browse_id: Some(self.channel_id),
             params: Some(self.tab.as_str().to_string()),
             continuation: None
@@ -248,16 +254,16 @@ impl<'a> GetVideosExtendedRequest<'a> {
 
         let req = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/browse", self.ip))
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
             .header("Host", "youtubei.googleapis.com")
             .header("Content-Type", "application/x-protobuf")
-            .header("X-Goog-Fieldmask", "contents.twoColumnBrowseResultsRenderer.tabs.tabRenderer.content.richGridRenderer.contents(richItemRenderer.content.videoRenderer(videoId,viewCountText.simpleText,lengthText.simpleText,publishedTimeText.simpleText,badges,upcomingEventData),continuationItemRenderer.continuationEndpoint.continuationCommand.token)")
-            .body(Full::new(Bytes::from(payload)))
+            .header("X-Goog-Fieldmask", "contents.twoColumnBrowseResultsRenderer.tabs.tabRenderer.content.richGridRenderer.contents(richItemRenderer.content(videoRenderer(videoId,viewCountText.simpleText,lengthText.simpleText,publishedTimeText.simpleText,badges,upcomingEventData),shortsLockupViewModel(entityId,onTap.innertubeCommand.reelWatchEndpoint.videoId,overlayMetadata.secondaryText.content),reelItemRenderer(videoId,viewCountText.simpleText)),continuationItemRenderer.continuationEndpoint.continuationCommand.token)")
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
 
-        match resp.status() {
+        match resp.status {
             StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
             StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
             StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
@@ -265,7 +271,7 @@ impl<'a> GetVideosExtendedRequest<'a> {
             status => Err(YouTubeError::UnknownStatusCode(status)),
         }?;
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
+        let body_bytes = resp.body;
         let response = youtube::BrowseResponse::decode(body_bytes)?;
 
         let mut videos = Vec::new();
@@ -283,18 +289,7 @@ impl<'a> GetVideosExtendedRequest<'a> {
                                         if let Some(content) = rich_item.content {
                                             if let Some(video) = content.video_renderer {
                                                 if video.upcoming_event_data.is_none() && !video.video_id.is_empty() {
-                                                    let (views, hidden_view_count) = if let Some(view_count) = video.view_count_text {
-                                                        let view_text = view_count.simple_text.trim();
-                                                        if view_text == "No views" {
-                                                            (0, false)
-                                                        } else {
-                                                            (parse_numeric_string(
-                                                                view_text.trim_end_matches(" views")
-                                                            ), false)
-                                                        }
-                                                    } else {
-                                                        (0, true)
-                                                    };
+                                                    let (views, hidden_view_count) = parse_view_count_text(video.view_count_text.as_ref().map(|text| text.simple_text.as_str()), locale);
 
                                                     // Get the first non-blacklisted badge label
                                                     let badge = video.badges.iter()
@@ -314,9 +309,10 @@ impl<'a> GetVideosExtendedRequest<'a> {
                                                         .as_ref()
                                                         .and_then(|lt| parse_length_text(&lt.simple_text));
 
-                                                    let approx_published_time = video.published_time_text
-                                                        .as_ref()
-                                                        .and_then(|pt| parse_published_time_text(&pt.simple_text));
+                                                    let (approx_published_time, published_time_precision) = parse_approx_published_time(
+                                                        video.published_time_text.as_ref().map(|text| text.simple_text.as_str()),
+                                                        locale
+                                                    );
 
                                                     videos.push(Video {
                                                         video_id: video.video_id,
@@ -324,10 +320,20 @@ impl<'a> GetVideosExtendedRequest<'a> {
                                                         hidden_view_count,
                                                         badge,
                                                         length_seconds,
-                                                        approx_published_time
+                                                        approx_published_time,
+                                                        published_time_precision
                                                     });
                                                 }
                                             }
+
+                                            // Shorts tab
+                                            if let Some(video) = content.shorts_lockup_view_model.and_then(|lockup| parse_shorts_lockup(lockup, locale)) {
+                                                videos.push(video);
+                                            }
+
+                                            if let Some(video) = content.reel_item_renderer.and_then(|reel| parse_reel_item(reel, locale)) {
+                                                videos.push(video);
+                                            }
                                         }
                                     }
 
@@ -353,28 +359,24 @@ impl<'a> GetVideosExtendedRequest<'a> {
     }
 }
 
-pub struct GetPopularVideosRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+pub struct GetPopularVideosRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub channel_id: String,
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for GetPopularVideosRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetPopularVideosRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
 
-impl<'a> GetPopularVideosRequest<'a> {
+impl<T: Transport> GetPopularVideosRequest<T> {
     pub async fn send(self) -> Result<(Vec<Video>, Option<String>), YouTubeError> {
+        let locale = self.client.locale(&self.fields);
+
         let request = youtube::BrowseRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { 
-                    client_name: 1, 
-                    client_version: "2.20240614.01.00".to_string() 
-                })
-            }),
+            context: Some(self.client.context(&self.fields)),
             browse_id: None,
             params: None,
             continuation: Some(generate_continuation_token(
@@ -388,16 +390,16 @@ impl<'a> GetPopularVideosRequest<'a> {
 
         let req = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/browse", self.ip))
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
             .header("Host", "youtubei.googleapis.com")
             .header("Content-Type", "application/x-protobuf")
             .header("X-Goog-Fieldmask", "onResponseReceivedActions.reloadContinuationItemsCommand.continuationItems(richItemRenderer.content.videoRenderer(videoId,viewCountText.simpleText,lengthText.simpleText,publishedTimeText.simpleText,badges),continuationItemRenderer.continuationEndpoint.continuationCommand.token)")
-            .body(Full::new(Bytes::from(payload)))
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
 
-        match resp.status() {
+        match resp.status {
             StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
             StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
             StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
@@ -405,7 +407,7 @@ impl<'a> GetPopularVideosRequest<'a> {
             status => Err(YouTubeError::UnknownStatusCode(status)),
         }?;
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
+        let body_bytes = resp.body;
         let response = youtube::BrowseResponse::decode(body_bytes)?;
 
         let mut videos = Vec::new();
@@ -419,18 +421,7 @@ impl<'a> GetPopularVideosRequest<'a> {
                         if let Some(content) = rich_item.content {
                             if let Some(video) = content.video_renderer {
                                 if video.upcoming_event_data.is_none() && !video.video_id.is_empty() {
-                                    let (views, hidden_view_count) = if let Some(view_count) = video.view_count_text {
-                                        let view_text = view_count.simple_text.trim();
-                                        if view_text == "No views" {
-                                            (0, false)
-                                        } else {
-                                            (parse_numeric_string(
-                                                view_text.trim_end_matches(" views")
-                                            ), false)
-                                        }
-                                    } else {
-                                        (0, true)
-                                    };
+                                    let (views, hidden_view_count) = parse_view_count_text(video.view_count_text.as_ref().map(|text| text.simple_text.as_str()), locale);
 
                                     // Get the first non-blacklisted badge label
                                     let badge = video.badges.iter()
@@ -450,9 +441,10 @@ impl<'a> GetPopularVideosRequest<'a> {
                                         .as_ref()
                                         .and_then(|lt| parse_length_text(&lt.simple_text));
 
-                                    let approx_published_time = video.published_time_text
-                                        .as_ref()
-                                        .and_then(|pt| parse_published_time_text(&pt.simple_text));
+                                    let (approx_published_time, published_time_precision) = parse_approx_published_time(
+                                        video.published_time_text.as_ref().map(|text| text.simple_text.as_str()),
+                                        locale
+                                    );
 
                                     videos.push(Video {
                                         video_id: video.video_id,
@@ -460,7 +452,8 @@ impl<'a> GetPopularVideosRequest<'a> {
                                         hidden_view_count,
                                         badge,
                                         length_seconds,
-                                        approx_published_time
+                                        approx_published_time,
+                                        published_time_precision
                                     });
                                 }
                             }
@@ -1,37 +1,30 @@
-use crate::{youtube, InnerTubeRequestFields, YouTubeError};
-use hyper_tls::HttpsConnector;
-use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
 use hyper::body::Bytes;
-use http_body_util::{BodyExt, Full};
 use hyper::{Method, Request, StatusCode};
 use prost::Message;
-use crate::utils::parse_numeric_string;
 use crate::models::Video;
 use crate::browse::videos::GetVideosResponse;
 use crate::browse::videos::BLACKLISTED_BADGE_LABELS;
-use crate::browse::videos::{parse_length_text, parse_published_time_text};
+use crate::browse::videos::{parse_approx_published_time, parse_length_text, parse_reel_item, parse_shorts_lockup, parse_view_count_text};
 
-pub struct GetVideosContinuationRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+pub struct GetVideosContinuationRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub continuation_token: String,
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for GetVideosContinuationRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetVideosContinuationRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
 
-impl<'a> GetVideosContinuationRequest<'a> {
+impl<T: Transport> GetVideosContinuationRequest<T> {
 
     pub async fn send(self) -> Result<GetVideosResponse, YouTubeError> {
         let request = youtube::BrowseRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { client_name: 1, client_version: "2.20240614.01.00".to_string() })
-            }),
+            context: Some(self.client.context(&self.fields)),
             browse_id: None,
             params: None,
             continuation: Some(self.continuation_token)
@@ -42,16 +35,16 @@ impl<'a> GetVideosContinuationRequest<'a> {
 
         let req = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/browse", self.ip))
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
             .header("Host", "youtubei.googleapis.com")
             .header("Content-Type", "application/x-protobuf")
             .header("X-Goog-Fieldmask", "onResponseReceivedActions.appendContinuationItemsAction.continuationItems(richItemRenderer.content.videoRenderer.videoId,continuationItemRenderer.continuationEndpoint.continuationCommand.token)")
-            .body(Full::new(Bytes::from(payload)))
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
 
-        match resp.status() {
+        match resp.status {
             StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
             StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
             StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
@@ -59,7 +52,7 @@ impl<'a> GetVideosContinuationRequest<'a> {
             status => Err(YouTubeError::UnknownStatusCode(status)),
         }?;
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
+        let body_bytes = resp.body;
         let response = youtube::BrowseResponse::decode(body_bytes)?;
 
         // Parse about videos data
@@ -104,28 +97,24 @@ impl<'a> GetVideosContinuationRequest<'a> {
 
 }
 
-pub struct GetVideosExtendedContinuationRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+pub struct GetVideosExtendedContinuationRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub continuation_token: String,
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for GetVideosExtendedContinuationRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetVideosExtendedContinuationRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
 
-impl<'a> GetVideosExtendedContinuationRequest<'a> {
+impl<T: Transport> GetVideosExtendedContinuationRequest<T> {
     pub async fn send(self) -> Result<(Vec<Video>, Option<String>), YouTubeError> {
+        let locale = self.client.locale(&self.fields);
+
         let request = youtube::BrowseRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { 
-                    client_name: 1, 
-                    client_version: "2.20240614.01.00".to_string() 
-                })
-            }),
+            context: Some(self.client.context(&self.fields)),
             browse_id: None,
             params: None,
             continuation: Some(self.continuation_token),
@@ -136,16 +125,16 @@ impl<'a> GetVideosExtendedContinuationRequest<'a> {
 
         let req = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/browse", self.ip))
+            .uri(format!("https://{}/youtubei/v1/browse", self.client.ip))
             .header("Host", "youtubei.googleapis.com")
             .header("Content-Type", "application/x-protobuf")
-            .header("X-Goog-Fieldmask", "onResponseReceivedActions.appendContinuationItemsAction.continuationItems(richItemRenderer.content.videoRenderer(videoId,viewCountText.simpleText,lengthText.simpleText,publishedTimeText.simpleText,badges),continuationItemRenderer.continuationEndpoint.continuationCommand.token)")
-            .body(Full::new(Bytes::from(payload)))
+            .header("X-Goog-Fieldmask", "onResponseReceivedActions.appendContinuationItemsAction.continuationItems(richItemRenderer.content(videoRenderer(videoId,viewCountText.simpleText,lengthText.simpleText,publishedTimeText.simpleText,badges,upcomingEventData),shortsLockupViewModel(entityId,onTap.innertubeCommand.reelWatchEndpoint.videoId,overlayMetadata.secondaryText.content),reelItemRenderer(videoId,viewCountText.simpleText)),continuationItemRenderer.continuationEndpoint.continuationCommand.token)")
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
 
-        match resp.status() {
+        match resp.status {
             StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
             StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
             StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
@@ -153,7 +142,7 @@ impl<'a> GetVideosExtendedContinuationRequest<'a> {
             status => Err(YouTubeError::UnknownStatusCode(status)),
         }?;
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
+        let body_bytes = resp.body;
         let response = youtube::BrowseResponse::decode(body_bytes)?;
 
         let mut videos = Vec::new();
@@ -168,18 +157,7 @@ impl<'a> GetVideosExtendedContinuationRequest<'a> {
                         if let Some(content) = rich_item.content {
                             if let Some(video) = content.video_renderer {
                                 if video.upcoming_event_data.is_none() && !video.video_id.is_empty() {
-                                    let (views, hidden_view_count) = if let Some(view_count) = video.view_count_text {
-                                        let view_text = view_count.simple_text.trim();
-                                        if view_text == "No views" {
-                                            (0, false)
-                                        } else {
-                                            (parse_numeric_string(
-                                                view_text.trim_end_matches(" views")
-                                            ), false)
-                                        }
-                                    } else {
-                                        (0, true)
-                                    };
+                                    let (views, hidden_view_count) = parse_view_count_text(video.view_count_text.as_ref().map(|text| text.simple_text.as_str()), locale);
 
                                     // Check if badges array exists and has at least one item
                                     let badge = video.badges.iter()
@@ -199,9 +177,10 @@ impl<'a> GetVideosExtendedContinuationRequest<'a> {
                                         .as_ref()
                                         .and_then(|lt| parse_length_text(&lt.simple_text));
 
-                                    let approx_published_time = video.published_time_text
-                                        .as_ref()
-                                        .and_then(|pt| parse_published_time_text(&pt.simple_text));
+                                    let (approx_published_time, published_time_precision) = parse_approx_published_time(
+                                        video.published_time_text.as_ref().map(|text| text.simple_text.as_str()),
+                                        locale
+                                    );
 
                                     videos.push(Video {
                                         video_id: video.video_id,
@@ -209,10 +188,20 @@ impl<'a> GetVideosExtendedContinuationRequest<'a> {
                                         hidden_view_count,
                                         badge,
                                         length_seconds,
-                                        approx_published_time
+                                        approx_published_time,
+                                        published_time_precision
                                     });
                                 }
                             }
+
+                            // Shorts tab
+                            if let Some(video) = content.shorts_lockup_view_model.and_then(|lockup| parse_shorts_lockup(lockup, locale)) {
+                                videos.push(video);
+                            }
+
+                            if let Some(video) = content.reel_item_renderer.and_then(|reel| parse_reel_item(reel, locale)) {
+                                videos.push(video);
+                            }
                         }
                     }
 
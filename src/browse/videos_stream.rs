@@ -0,0 +1,135 @@
+use crate::{InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
+use crate::models::Video;
+use crate::browse::videos::{ChannelTab, GetVideosExtendedRequest};
+use crate::browse::videos_continued::GetVideosExtendedContinuationRequest;
+use futures::stream::{self, Stream};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+type StopPredicate = Arc<dyn Fn(&Video) -> bool + Send + Sync>;
+
+/// Pages through a channel tab, following continuation tokens until they run out or a limit is hit.
+pub struct GetVideosStreamRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub channel_id: String,
+    pub tab: ChannelTab,
+    pub max_items: Option<usize>,
+    pub max_pages: Option<usize>,
+    pub(crate) stop_when: Option<StopPredicate>,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetVideosStreamRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+enum Page {
+    First,
+    Continuation(String),
+    Done,
+}
+
+struct StreamState<T: Transport> {
+    request: GetVideosStreamRequest<T>,
+    next_page: Page,
+    buffer: VecDeque<Video>,
+    pages: usize,
+    items: usize,
+}
+
+impl<T: Transport> StreamState<T> {
+    async fn fetch_page(&mut self) -> Result<(), YouTubeError> {
+        let request = &self.request;
+        let (videos, continuation) = match std::mem::replace(&mut self.next_page, Page::Done) {
+            Page::First => GetVideosExtendedRequest {
+                client: request.client.clone(),
+                fields: request.fields.clone(),
+                channel_id: request.channel_id.clone(),
+                tab: request.tab.clone(),
+            }.send().await?,
+            Page::Continuation(continuation_token) => GetVideosExtendedContinuationRequest {
+                client: request.client.clone(),
+                fields: request.fields.clone(),
+                continuation_token,
+            }.send().await?,
+            Page::Done => return Ok(()),
+        };
+
+        self.pages += 1;
+        self.buffer.extend(videos);
+
+        if let Some(token) = continuation {
+            if request.max_pages.is_none_or(|max_pages| self.pages < max_pages) {
+                self.next_page = Page::Continuation(token);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn next_video(&mut self) -> Option<Result<Video, YouTubeError>> {
+        if self.request.max_items.is_some_and(|max_items| self.items >= max_items) {
+            return None;
+        }
+
+        // Pages can come back empty while still carrying a continuation
+        while self.buffer.is_empty() {
+            if matches!(self.next_page, Page::Done) {
+                return None;
+            }
+            if let Err(e) = self.fetch_page().await {
+                self.next_page = Page::Done;
+                return Some(Err(e));
+            }
+        }
+
+        let video = self.buffer.pop_front()?;
+        if self.request.stop_when.as_ref().is_some_and(|stop_when| stop_when(&video)) {
+            self.next_page = Page::Done;
+            self.buffer.clear();
+            return None;
+        }
+
+        self.items += 1;
+        Some(Ok(video))
+    }
+}
+
+impl<T: Transport> GetVideosStreamRequest<T> {
+    /// Stops after yielding this many videos.
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Stops after fetching this many pages, including the first one.
+    pub fn with_max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = Some(max_pages);
+        self
+    }
+
+    /// Ends the stream at the first video `stop_when` returns true for. That video isn't yielded
+    /// and no further pages are fetched.
+    pub fn with_stop_when(mut self, stop_when: impl Fn(&Video) -> bool + Send + Sync + 'static) -> Self {
+        self.stop_when = Some(Arc::new(stop_when));
+        self
+    }
+
+    /// Pages are fetched lazily as the stream is polled. The stream ends after the first error.
+    pub fn stream(self) -> impl Stream<Item = Result<Video, YouTubeError>> + Send {
+        let state = StreamState {
+            next_page: if self.max_pages == Some(0) { Page::Done } else { Page::First },
+            request: self,
+            buffer: VecDeque::new(),
+            pages: 0,
+            items: 0,
+        };
+
+        stream::unfold(state, |mut state| async move {
+            state.next_video().await.map(|video| (video, state))
+        })
+    }
+}
@@ -0,0 +1,207 @@
+use crate::youtube;
+use crate::locale::Locale;
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientName {
+    Web,
+    Mweb,
+    Tvhtml5,
+    Android,
+    Ios,
+    WebCreator,
+    WebRemix,
+}
+
+impl ClientName {
+    /// The numeric client name sent in the request context.
+    pub fn id(&self) -> i32 {
+        match self {
+            ClientName::Web => 1,
+            ClientName::Mweb => 2,
+            ClientName::Android => 3,
+            ClientName::Ios => 5,
+            ClientName::Tvhtml5 => 7,
+            ClientName::WebCreator => 62,
+            ClientName::WebRemix => 67,
+        }
+    }
+
+    pub fn default_version(&self) -> &'static str {
+        match self {
+            ClientName::Web => "2.20240614.01.00",
+            ClientName::Mweb => "2.20240614.01.00",
+            ClientName::Android => "19.09.37",
+            ClientName::Ios => "19.09.3",
+            ClientName::Tvhtml5 => "7.20250126.17.00",
+            ClientName::WebCreator => "1.20250527.06.00",
+            ClientName::WebRemix => "1.20240612.01.00",
+        }
+    }
+
+    // Only the app clients need a matching user agent, browser clients work without one
+    fn default_user_agent(&self) -> Option<&'static str> {
+        match self {
+            ClientName::Android => Some("com.google.android.youtube/19.09.37 (Linux; U; Android 11) gzip"),
+            ClientName::Ios => Some("com.google.ios.youtube/19.09.3 (iPhone14,3; U; CPU iOS 15_6 like Mac OS X)"),
+            _ => None,
+        }
+    }
+}
+
+/// The client identity and locale sent in the context of every request.
+///
+/// `InnertubeClient` holds a default profile which requests use unless one is
+/// passed with `with_client_profile`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientProfile {
+    pub client_name: ClientName,
+    pub client_version: String,
+    pub hl: Option<String>,
+    pub gl: Option<String>,
+    pub visitor_data: Option<String>,
+    pub user_agent: Option<String>,
+    pub time_zone: Option<String>,
+}
+
+impl Default for ClientProfile {
+    fn default() -> Self {
+        ClientProfile::new(ClientName::Web)
+    }
+}
+
+impl ClientProfile {
+    pub fn new(client_name: ClientName) -> Self {
+        ClientProfile {
+            client_name,
+            client_version: client_name.default_version().to_string(),
+            hl: None,
+            gl: None,
+            visitor_data: None,
+            user_agent: client_name.default_user_agent().map(|ua| ua.to_string()),
+            time_zone: None,
+        }
+    }
+
+    pub fn web() -> Self {
+        ClientProfile::new(ClientName::Web)
+    }
+
+    pub fn mweb() -> Self {
+        ClientProfile::new(ClientName::Mweb)
+    }
+
+    pub fn tvhtml5() -> Self {
+        ClientProfile::new(ClientName::Tvhtml5)
+    }
+
+    pub fn android() -> Self {
+        ClientProfile::new(ClientName::Android)
+    }
+
+    pub fn ios() -> Self {
+        ClientProfile::new(ClientName::Ios)
+    }
+
+    pub fn web_creator() -> Self {
+        ClientProfile::new(ClientName::WebCreator)
+    }
+
+    pub fn web_remix() -> Self {
+        ClientProfile::new(ClientName::WebRemix)
+    }
+
+    pub fn with_version(mut self, client_version: &str) -> Self {
+        self.client_version = client_version.to_string();
+        self
+    }
+
+    pub fn with_hl(mut self, hl: &str) -> Self {
+        self.hl = Some(hl.to_string());
+        self
+    }
+
+    pub fn with_gl(mut self, gl: &str) -> Self {
+        self.gl = Some(gl.to_string());
+        self
+    }
+
+    pub fn with_visitor_data(mut self, visitor_data: &str) -> Self {
+        self.visitor_data = Some(visitor_data.to_string());
+        self
+    }
+
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    pub fn with_time_zone(mut self, time_zone: &str) -> Self {
+        self.time_zone = Some(time_zone.to_string());
+        self
+    }
+
+    /// Switches to another client while keeping the locale and visitor settings.
+    /// Used by endpoints that only answer to a specific client (e.g. TVHTML5 for subscriptions).
+    pub fn as_client(&self, client_name: ClientName) -> Self {
+        if self.client_name == client_name {
+            return self.clone();
+        }
+
+        ClientProfile {
+            client_name,
+            client_version: client_name.default_version().to_string(),
+            user_agent: client_name.default_user_agent().map(|ua| ua.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// Switches the interface language to English while keeping the client and region.
+    /// Used by endpoints whose responses are classified by their English text.
+    pub fn in_english(&self) -> Self {
+        ClientProfile {
+            hl: Some("en".to_string()),
+            ..self.clone()
+        }
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.hl.as_deref().map(Locale::from_hl).unwrap_or_default()
+    }
+
+    pub(crate) fn context(&self) -> youtube::Context {
+        youtube::Context {
+            client: Some(youtube::Client {
+                hl: self.hl.clone().unwrap_or_default(),
+                gl: self.gl.clone().unwrap_or_default(),
+                visitor_data: self.visitor_data.clone().unwrap_or_default(),
+                user_agent: self.user_agent.clone().unwrap_or_default(),
+                client_name: self.client_name.id(),
+                client_version: self.client_version.clone(),
+                time_zone: self.time_zone.clone().unwrap_or_default(),
+            })
+        }
+    }
+
+    // For the endpoints that only accept JSON
+    pub(crate) fn json_context(&self) -> Value {
+        let mut client = json!({
+            "clientName": self.client_name.id(),
+            "clientVersion": self.client_version,
+        });
+
+        for (key, value) in [
+            ("hl", &self.hl),
+            ("gl", &self.gl),
+            ("visitorData", &self.visitor_data),
+            ("userAgent", &self.user_agent),
+            ("timeZone", &self.time_zone),
+        ] {
+            if let Some(value) = value {
+                client[key] = json!(value);
+            }
+        }
+
+        json!({ "client": client })
+    }
+}
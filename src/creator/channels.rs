@@ -1,10 +1,8 @@
 use crate::models::*;
-use crate::{youtube, InnerTubeRequestFields, YouTubeError};
-use hyper_tls::HttpsConnector;
-use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::client_profile::ClientName;
+use crate::transport::{HyperTransport, Transport};
 use hyper::body::Bytes;
-use http_body_util::{BodyExt, Full};
 use hyper::{Method, Request, StatusCode};
 use prost::Message;
 use scylla::frame::value::CqlTimestamp;
@@ -18,20 +16,19 @@ pub struct HiddenUser {
     pub avatar_url: Option<String>,
 }
 
-pub struct GetCreatorChannelsRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+pub struct GetCreatorChannelsRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub channel_ids: Vec<String>,
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for GetCreatorChannelsRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetCreatorChannelsRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
 
-impl<'a> GetCreatorChannelsRequest<'a> {
+impl<T: Transport> GetCreatorChannelsRequest<T> {
 
     fn convert_timestamp(seconds: i64, nanos: i64) -> i64 {
         seconds * 1_000_000_000 + nanos
@@ -39,9 +36,7 @@ impl<'a> GetCreatorChannelsRequest<'a> {
 
     pub async fn send(self) -> Result<Vec<Channel>, YouTubeError> {
         let request = youtube::GetCreatorChannelsRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { client_name: 1, client_version: "2.20240614.01.00".to_string() })
-            }),
+            context: Some(self.client.context(&self.fields)),
             channel_ids: self.channel_ids,
             mask: Some(youtube::CreatorChannelMask {
                 channel_id: true,
@@ -53,6 +48,9 @@ impl<'a> GetCreatorChannelsRequest<'a> {
                     all: true
                 }),
                 time_created_seconds: true,
+                content_owner_association: Some(youtube::creator_channel_mask::ContentOwnerAssociationMask{
+                    all: true
+                }),
                 is_name_verified: true,
                 channel_handle: true,
                 comments_settings: None
@@ -64,23 +62,23 @@ impl<'a> GetCreatorChannelsRequest<'a> {
 
         let req = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/creator/get_creator_channels", self.ip))
+            .uri(format!("https://{}/youtubei/v1/creator/get_creator_channels", self.client.ip))
             .header("Host", "youtubei.googleapis.com")
             .header("Content-Type", "application/x-protobuf")
-            .header("Authorization", self.fields.authorization.unwrap())
+            .header("Authorization", self.fields.authorization.as_deref().unwrap())
             .header("X-Goog-Fieldmask", "channels(channelId,title,thumbnailDetails.thumbnails.url,metric,timeCreatedSeconds,contentOwnerAssociation,isNameVerified,channelHandle)")
-            .body(Full::new(Bytes::from(payload)))
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
 
-        let status = resp.status();
+        let status = resp.status;
         
         match status {
             StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
             StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
             StatusCode::UNAUTHORIZED => {
-                let body_bytes = resp.into_body().collect().await?.to_bytes();
+                let body_bytes = resp.body;
                 let body_str = String::from_utf8_lossy(&body_bytes);
                 eprintln!("Unauthorized error response: {}", body_str);
                 return Err(YouTubeError::Unauthorized);
@@ -93,14 +91,14 @@ impl<'a> GetCreatorChannelsRequest<'a> {
             }
             StatusCode::OK => Ok(()),
             status => {
-                let body_bytes = resp.into_body().collect().await?.to_bytes();
+                let body_bytes = resp.body;
                 let body_str = String::from_utf8_lossy(&body_bytes);
                 eprintln!("Unknown status code {}: {}", status.as_u16(), body_str);
                 return Err(YouTubeError::UnknownStatusCode(status));
             },
         }?;
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
+        let body_bytes = resp.body;
         let decoded_bytes = BASE64.decode(body_bytes)
         .map_err(|e| YouTubeError::Other(Box::new(e)))?;
         let response = youtube::GetCreatorChannelsResponse::decode(Bytes::from(decoded_bytes))?;
@@ -127,10 +125,7 @@ impl<'a> GetCreatorChannelsRequest<'a> {
                 has_business_email: false,
                 links: Vec::new(),
                 tags: Vec::new(),
-                deleted: false,
-                hidden: false,
-                terminated: false,
-                termination_reason: String::new(),
+                status: ChannelStatus::Active,
                 no_index: false,
                 unlisted: false,
                 family_safe: false,
@@ -174,6 +169,26 @@ impl<'a> GetCreatorChannelsRequest<'a> {
             // Convert creation timestamp
             channel.created_at = CqlTimestamp(channel_data.time_created_seconds * 1000); // Convert seconds to milliseconds
 
+            // Only present on channels managed by a content owner (MCN/CMS)
+            if let Some(association) = channel_data.content_owner_association {
+                let permissions = association.permissions.unwrap_or_default();
+
+                channel.cms_association = Some(ContentOwnerAssociation {
+                    cms_id: association.external_content_owner_id,
+                    created_at: association.create_time
+                        .map(|time| Self::convert_timestamp(time.seconds, time.nanos))
+                        .unwrap_or_default(),
+                    activated_at: association.activate_time
+                        .map(|time| Self::convert_timestamp(time.seconds, time.nanos))
+                        .unwrap_or_default(),
+                    can_web_claim: permissions.can_web_claim,
+                    can_view_revenue: permissions.can_view_revenue,
+                    can_enable_cid: permissions.can_enable_cid,
+                    disable_ad_blocking_settings: permissions.disable_ad_blocking_settings,
+                    default_channel: association.is_default_channel,
+                });
+            }
+
             channels.push(channel);
         }
 
@@ -182,29 +197,24 @@ impl<'a> GetCreatorChannelsRequest<'a> {
 
 }
 
-pub struct GetHiddenUsersRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+pub struct GetHiddenUsersRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub channel_id: String,
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for GetHiddenUsersRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetHiddenUsersRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
 
-impl<'a> GetHiddenUsersRequest<'a> {
+impl<T: Transport> GetHiddenUsersRequest<T> {
     pub async fn send(self) -> Result<Vec<HiddenUser>, YouTubeError> {
         // Create JSON payload instead of protobuf
+        let profile = self.client.profile_as(&self.fields, ClientName::WebCreator);
         let json_payload = json!({
-            "context": {
-                "client": {
-                    "clientName": 62,
-                    "clientVersion": "1.20250731.01.00"
-                }
-            },
+            "context": profile.json_context(),
             "channelIds": [self.channel_id],
             "mask": {
                 "commentsSettings": {
@@ -220,34 +230,34 @@ impl<'a> GetHiddenUsersRequest<'a> {
 
         let mut req_builder = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/creator/get_creator_channels?alt=json", self.ip))
+            .uri(format!("https://{}/youtubei/v1/creator/get_creator_channels?alt=json", self.client.ip))
             .header("Host", "studio.youtube.com")
             .header("Origin", "https://studio.youtube.com")
             .header("Content-Type", "application/json")
             .header("X-Goog-Fieldmask", "channels.commentsSettings.hiddenUsers");
 
         // Add authorization header if provided
-        if let Some(token) = self.fields.authorization {
+        if let Some(token) = self.fields.authorization.as_deref() {
             req_builder = req_builder.header("Authorization", token);
         }
 
         // Add cookie if provided
-        if let Some(cookie) = self.fields.cookie {
+        if let Some(cookie) = self.fields.cookie.as_deref() {
             req_builder = req_builder.header("Cookie", cookie);
         }
 
         let req = req_builder
-            .body(Full::new(Bytes::from(payload)))
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, &profile).await?;
 
-        let status = resp.status();
+        let status = resp.status;
         match status {
             StatusCode::NOT_FOUND => return Err(YouTubeError::NotFound),
             StatusCode::TOO_MANY_REQUESTS => return Err(YouTubeError::Ratelimited),
             StatusCode::UNAUTHORIZED => {
-                let body_bytes = resp.into_body().collect().await?.to_bytes();
+                let body_bytes = resp.body;
                 let body_str = String::from_utf8_lossy(&body_bytes);
                 eprintln!("Unauthorized error response: {}", body_str);
                 return Err(YouTubeError::Unauthorized);
@@ -256,14 +266,14 @@ impl<'a> GetHiddenUsersRequest<'a> {
             StatusCode::SERVICE_UNAVAILABLE => return Err(YouTubeError::InternalServerError),
             StatusCode::OK => (), // Continue processing
             status => {
-                let body_bytes = resp.into_body().collect().await?.to_bytes();
+                let body_bytes = resp.body;
                 let body_str = String::from_utf8_lossy(&body_bytes);
                 tracing::error!("Unknown status code {}: {}", status.as_u16(), body_str);
                 return Err(YouTubeError::UnknownStatusCode(status));
             }
         };
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
+        let body_bytes = resp.body;
         let body_str = String::from_utf8_lossy(&body_bytes);
         let json_response: Value = serde_json::from_str(&body_str)
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
@@ -1,12 +1,10 @@
-use crate::{youtube, InnerTubeRequestFields, YouTubeError};
-use hyper_tls::HttpsConnector;
-use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::client_profile::ClientName;
+use crate::transport::{HyperTransport, Transport};
 use hyper::body::Bytes;
-use http_body_util::{BodyExt, Full};
 use hyper::{Method, Request, StatusCode};
 use prost::Message;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 
 // JSON response structures for search_public_creator_entities
 #[derive(Deserialize, Debug)]
@@ -20,28 +18,24 @@ struct SearchCreatorChannel {
     channel_id: String,
 }
 
-pub struct SearchPublicCreatorEntitiesRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+pub struct SearchPublicCreatorEntitiesRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub query: String,
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for SearchPublicCreatorEntitiesRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for SearchPublicCreatorEntitiesRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
 
-impl<'a> SearchPublicCreatorEntitiesRequest<'a> {
+impl<T: Transport> SearchPublicCreatorEntitiesRequest<T> {
     pub async fn send(self) -> Result<Vec<String>, YouTubeError> {
+        let profile = self.client.profile_as(&self.fields, ClientName::WebCreator);
+
         let request = youtube::SearchPublicCreatorEntitiesRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { 
-                    client_name: 62, 
-                    client_version: "1.20250527.06.00".to_string() 
-                })
-            }),
+            context: Some(profile.context()),
             query: self.query,
             filter: Some(crate::youtube::search_public_creator_entities_request::Filter{
                 restrict_result_type: 10
@@ -54,29 +48,29 @@ impl<'a> SearchPublicCreatorEntitiesRequest<'a> {
 
         let mut req_builder = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/creator/search_public_creator_entities?alt=json", self.ip))
+            .uri(format!("https://{}/youtubei/v1/creator/search_public_creator_entities?alt=json", self.client.ip))
             .header("Host", "studio.youtube.com")
             .header("Content-Type", "application/x-protobuf")
             .header("Origin", "https://studio.youtube.com")
             .header("X-Goog-Fieldmask", "channels(channelId)");
 
         // Add bearer token if provided
-        if let Some(token) = self.fields.authorization {
+        if let Some(token) = self.fields.authorization.as_deref() {
             req_builder = req_builder.header("Authorization", token);
         }
 
         // Add cookie if provided
-        if let Some(cookie) = self.fields.cookie {
+        if let Some(cookie) = self.fields.cookie.as_deref() {
             req_builder = req_builder.header("Cookie", cookie);
         }
 
         let req = req_builder
-            .body(Full::new(Bytes::from(payload)))
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, &profile).await?;
 
-        let status = resp.status();
+        let status = resp.status;
         match status {
             StatusCode::NOT_FOUND => return Err(YouTubeError::NotFound),
             StatusCode::TOO_MANY_REQUESTS => return Err(YouTubeError::Ratelimited),
@@ -85,14 +79,14 @@ impl<'a> SearchPublicCreatorEntitiesRequest<'a> {
             StatusCode::OK => (), // Continue processing
             _ => {
                 // For unknown status codes, collect and print the response for debugging
-                let body_bytes = resp.into_body().collect().await?.to_bytes();
+                let body_bytes = resp.body;
                 let body_str = std::str::from_utf8(&body_bytes).unwrap_or("<invalid utf8>");
                 println!("Unknown status code {} with response: {}", status, body_str);
                 return Err(YouTubeError::UnknownStatusCode(status));
             }
         };
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
+        let body_bytes = resp.body;
         
         // Parse as JSON instead of protobuf
         let body_str = std::str::from_utf8(&body_bytes)
@@ -0,0 +1,207 @@
+use crate::browse::channel::ALL_COUNTRIES;
+use crate::browse::conditional::ConditionalRedirectResult;
+use crate::models::PlayabilityStatus;
+use crate::proxy::Proxy;
+use crate::transport::{HyperTransport, Transport};
+use crate::{InnertubeClient, YouTubeError};
+use futures::stream::{self, StreamExt};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+/// What a channel or video looks like from one country.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeoAvailability {
+    Available,
+    Blocked,
+    /// The channel redirects to another channel (the browse id) in this country.
+    Redirected(String),
+    /// The video can't be played for a reason that has nothing to do with the country,
+    /// e.g. it's private or was removed.
+    Unplayable(PlayabilityStatus),
+}
+
+#[derive(Debug, Clone)]
+pub struct CountryAvailability {
+    pub country_code: String,
+    /// Proxy the country was checked through
+    pub proxy_url: String,
+    pub availability: GeoAvailability,
+    /// Whether the microformat lists the country as blocked, `None` if it has no country list
+    pub listed_blocked: Option<bool>,
+}
+
+impl CountryAvailability {
+    /// False if what was seen through the proxy contradicts the microformat.
+    pub fn matches_microformat(&self) -> bool {
+        match (self.listed_blocked, &self.availability) {
+            (None, _) | (_, GeoAvailability::Unplayable(_)) => true,
+            (Some(listed_blocked), availability) => listed_blocked == (*availability == GeoAvailability::Blocked),
+        }
+    }
+}
+
+/// A proxy that couldn't be checked.
+#[derive(Debug)]
+pub struct GeoSweepError {
+    pub proxy_url: String,
+    pub error: YouTubeError,
+}
+
+#[derive(Debug)]
+pub struct GeoReport {
+    /// Keyed by country code. When several proxies exit in the same country, the first one in the
+    /// sweep's list is used.
+    pub countries: BTreeMap<String, CountryAvailability>,
+    /// Countries the microformat doesn't list as available, sorted
+    pub blocked_countries: Vec<String>,
+    pub errors: Vec<GeoSweepError>,
+}
+
+impl GeoReport {
+    /// Countries where the observed availability disagrees with the microformat.
+    pub fn mismatches(&self) -> impl Iterator<Item = &CountryAvailability> {
+        self.countries.values().filter(|country| !country.matches_microformat())
+    }
+}
+
+/// Checks a channel or video from the country of every proxy in a list.
+///
+/// The country each proxy exits in is detected on first use and remembered, so one sweep can be
+/// reused for many channels and videos.
+pub struct GeoSweep<T: Transport = HyperTransport> {
+    client: InnertubeClient<T>,
+    proxy_urls: Vec<String>,
+    concurrency: usize,
+    countries: Mutex<HashMap<String, String>>,
+}
+
+impl<T: Transport> GeoSweep<T> {
+    pub fn new(client: InnertubeClient<T>, proxy_urls: Vec<String>) -> Self {
+        GeoSweep {
+            client,
+            proxy_urls,
+            concurrency: 8,
+            countries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// How many proxies are checked at once. Defaults to 8.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Country codes detected so far, keyed by proxy URL.
+    pub fn countries(&self) -> HashMap<String, String> {
+        self.countries.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    async fn country(&self, proxy_url: &str) -> Result<String, YouTubeError> {
+        if let Some(country_code) = self.countries.lock().unwrap_or_else(|e| e.into_inner()).get(proxy_url) {
+            return Ok(country_code.clone());
+        }
+
+        let country_code = self.client.detect_country_code(proxy_url.to_string()).send().await?.country_code;
+        self.countries.lock().unwrap_or_else(|e| e.into_inner())
+            .insert(proxy_url.to_string(), country_code.clone());
+        Ok(country_code)
+    }
+
+    async fn sweep<F, Fut>(&self, blocked_countries: Vec<String>, listed: bool, check: F) -> GeoReport
+    where
+        F: Fn(String) -> Fut,
+        Fut: std::future::Future<Output = Result<GeoAvailability, YouTubeError>>,
+    {
+        let check = &check;
+        let results: Vec<_> = stream::iter(self.proxy_urls.clone())
+            .map(|proxy_url| async move {
+                let result = match self.country(&proxy_url).await {
+                    Ok(country_code) => check(proxy_url.clone()).await
+                        .map(|availability| (country_code, availability)),
+                    Err(e) => Err(e),
+                };
+                (proxy_url, result)
+            })
+            .buffered(self.concurrency)
+            .collect()
+            .await;
+
+        let mut report = GeoReport {
+            countries: BTreeMap::new(),
+            blocked_countries,
+            errors: Vec::new(),
+        };
+        report.blocked_countries.sort();
+
+        for (proxy_url, result) in results {
+            match result {
+                Ok((country_code, availability)) => {
+                    let listed_blocked = listed.then(|| report.blocked_countries.binary_search(&country_code).is_ok());
+                    report.countries.entry(country_code.clone()).or_insert(CountryAvailability {
+                        country_code,
+                        proxy_url,
+                        availability,
+                        listed_blocked,
+                    });
+                }
+                Err(error) => {
+                    tracing::warn!("Geo sweep through {} failed: {}", proxy_url, error);
+                    report.errors.push(GeoSweepError { proxy_url, error });
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Checks whether a channel is available, blocked or redirected from every proxy's country.
+    /// The blocked countries are taken from the channel's microformat, fetched by the sweep's client.
+    pub async fn channel(&self, channel_id: String) -> Result<GeoReport, YouTubeError> {
+        let (_, blocked_countries) = self.client.get_channel(channel_id.clone()).send_with_country_list().await?;
+
+        // Channels without a microformat say nothing about where they're blocked
+        let listed = blocked_countries.is_some();
+        let report = self.sweep(blocked_countries.unwrap_or_default(), listed, |proxy_url| {
+            let request = self.client.resolve_conditional_redirect(proxy_url, channel_id.clone());
+            async move {
+                Ok(match request.send().await? {
+                    None => GeoAvailability::Available,
+                    Some(ConditionalRedirectResult::Blocked) => GeoAvailability::Blocked,
+                    Some(ConditionalRedirectResult::Channel(browse_id)) => GeoAvailability::Redirected(browse_id),
+                })
+            }
+        }).await;
+
+        Ok(report)
+    }
+
+    /// Checks whether a video plays from every proxy's country.
+    /// The blocked countries are taken from the video's microformat, fetched by the sweep's client.
+    pub async fn video(&self, video_id: String) -> Result<GeoReport, YouTubeError> {
+        let video = self.client.get_video(video_id.clone()).send().await?;
+
+        // Videos without a country list say nothing about where they're blocked
+        let listed = !video.available_countries.is_empty();
+        let blocked_countries = match listed {
+            true => ALL_COUNTRIES.iter()
+                .filter(|country| !video.available_countries.iter().any(|available| available == *country))
+                .map(|country| country.to_string())
+                .collect(),
+            false => Vec::new(),
+        };
+
+        let report = self.sweep(blocked_countries, listed, |proxy_url| {
+            let video_id = video_id.clone();
+            async move {
+                let client = self.client.clone().with_proxy(Proxy::parse(&proxy_url)?);
+                Ok(match client.get_video(video_id).send().await?.playability {
+                    PlayabilityStatus::Ok => GeoAvailability::Available,
+                    PlayabilityStatus::GeoBlocked => GeoAvailability::Blocked,
+                    status => GeoAvailability::Unplayable(status),
+                })
+            }
+        }).await;
+
+        Ok(report)
+    }
+}
@@ -1,28 +1,24 @@
-use crate::{youtube, InnerTubeRequestFields, YouTubeError};
-use hyper_tls::HttpsConnector;
-use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
 use hyper::body::Bytes;
-use http_body_util::{BodyExt, Full};
 use hyper::{Method, Request, StatusCode};
 use prost::Message;
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 
-pub struct UpdateHideUserStatusRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+pub struct UpdateHideUserStatusRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub channel_id: String,
     pub hide_user: bool,
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for UpdateHideUserStatusRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for UpdateHideUserStatusRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
 
-impl<'a> UpdateHideUserStatusRequest<'a> {
+impl<T: Transport> UpdateHideUserStatusRequest<T> {
     pub async fn send(self) -> Result<(), YouTubeError> {
         let user_id = if self.channel_id.len() >= 2 {
             self.channel_id[2..].to_string()
@@ -53,12 +49,7 @@ impl<'a> UpdateHideUserStatusRequest<'a> {
 
         // Create the flag request
         let request = youtube::FlagRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client {
-                    client_name: 1,
-                    client_version: "2.20240614.01.00".to_string()
-                })
-            }),
+            context: Some(self.client.context(&self.fields)),
             action: action_b64,
         };
 
@@ -68,38 +59,38 @@ impl<'a> UpdateHideUserStatusRequest<'a> {
 
         let mut req_builder = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/flag/flag", self.ip))
+            .uri(format!("https://{}/youtubei/v1/flag/flag", self.client.ip))
             .header("Host", "www.youtube.com")
-            .header("Cookie", self.fields.cookie.unwrap())
-            .header("Authorization", self.fields.authorization.unwrap())
+            .header("Cookie", self.fields.cookie.as_deref().unwrap())
+            .header("Authorization", self.fields.authorization.as_deref().unwrap())
             .header("Origin", "https://www.youtube.com")
             .header("Content-Type", "application/x-protobuf")
             .header("X-Goog-Encode-Response-If-Executable", "base64");
 
         // Add authorization header if provided
-        if let Some(token) = self.fields.authorization {
+        if let Some(token) = self.fields.authorization.as_deref() {
             req_builder = req_builder.header("Authorization", token);
         }
 
         // Add cookie if provided
-        if let Some(cookie) = self.fields.cookie {
+        if let Some(cookie) = self.fields.cookie.as_deref() {
             req_builder = req_builder.header("Cookie", cookie);
         }
 
         let req = req_builder
-            .body(Full::new(Bytes::from(payload)))
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
 
-        match resp.status() {
+        match resp.status {
             StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
             StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
             StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
             StatusCode::INTERNAL_SERVER_ERROR => Err(YouTubeError::InternalServerError),
             StatusCode::OK => Ok(()),
             status => {
-                let body_bytes = resp.into_body().collect().await?.to_bytes();
+                let body_bytes = resp.body;
                 let body_str = String::from_utf8_lossy(&body_bytes);
                 eprintln!("Unknown status code {}: {}", status.as_u16(), body_str);
                 Err(YouTubeError::UnknownStatusCode(status))
@@ -1,22 +1,40 @@
 pub mod utils;
+pub mod transport;
+pub mod client_profile;
+pub mod locale;
+pub mod retry;
+pub mod pool;
+pub mod address;
+pub mod proxy;
+pub mod geo;
 
 use std::error::Error;
+use std::sync::Arc;
+use std::net::IpAddr;
 use browse::videos_continued::GetVideosExtendedContinuationRequest;
+use browse::videos_stream::GetVideosStreamRequest;
 use hyper::body::Bytes;
 use hyper_tls::HttpsConnector;
 use hyper_util::client::legacy::Client;
 use hyper_util::client::legacy::connect::HttpConnector;
 use http_body_util::Full;
 use hyper::StatusCode;
-use rand::Rng;
-use resolve_url::ResolveUrlRequest;
+use resolve_url::{ResolveManyRequest, ResolveUrlRequest};
 use thiserror::Error;
 use native_tls::TlsConnector;
+use transport::{HyperTransport, Transport, TransportResponse};
+use retry::{RetryCounter, RetryPolicy, RetryStats};
+use proxy::{Proxy, ProxyError};
+use hyper::Request;
+use hyper::header::{HeaderValue, USER_AGENT};
+use client_profile::{ClientName, ClientProfile};
+use locale::Locale;
 use hyper_util::rt::TokioExecutor;
 
 #[cfg(test)]
 mod tests;
 
+#[allow(clippy::all)]
 mod youtube {
     include!(concat!(env!("OUT_DIR"), "/youtube.innertube.rs"));
 }
@@ -32,13 +50,23 @@ use crate::browse::videos::GetVideosExtendedRequest;
 use crate::browse::videos::GetPopularVideosRequest;
 use crate::browse::videos_continued::GetVideosContinuationRequest;
 use crate::browse::subscriptions::HasPublicSubscriptionsRequest;
+use crate::browse::playlist::GetPlaylistRequest;
+use crate::browse::channel_tab::{CollectionTab, GetChannelTabRequest, GetChannelTabContinuationRequest};
+use crate::browse::community::{GetCommunityPostsRequest, GetCommunityPostsContinuationRequest};
+use crate::browse::playlist_continued::GetPlaylistContinuationRequest;
 use crate::creator::channels::{GetCreatorChannelsRequest, GetHiddenUsersRequest};
 use crate::creator::search::SearchPublicCreatorEntitiesRequest;
-use crate::next::watch_next::GetWatchNextRequest;
+use crate::next::watch_next::{GetWatchNextContinuationRequest, GetWatchNextRequest};
+use crate::next::video_info::GetVideoInfoRequest;
+use crate::next::comments::{CommentSort, GetCommentsRequest, GetCommentsContinuationRequest};
 use crate::hide_user::UpdateHideUserStatusRequest;
+use crate::search::{SearchRequest, SearchContinuationRequest};
+use crate::player::{GetPublishTimesRequest, GetVideoRequest};
 pub mod models;
 pub mod resolve_url;
 pub mod hide_user;
+pub mod search;
+pub mod player;
 
 #[derive(Error, Debug)]
 pub enum ClientError {
@@ -47,17 +75,25 @@ pub enum ClientError {
     #[error("TLS error: {0}")]
     TlsError(#[from] native_tls::Error),
     #[error("Invalid IP: {0}")]
-    InvalidIp(String)
+    InvalidIp(String),
+    #[error("Address error: {0}")]
+    AddressError(#[from] address::AddressError)
 }
 
 pub fn initialize_client(subnet: Option<&str>, subnet_id: Option<u16>) -> Result<Client<HttpsConnector<HttpConnector>, Full<Bytes>>, ClientError> {
+    let local_address = match subnet {
+        Some(subnet) => Some(crate::utils::get_rand_ipv6(subnet, subnet_id.unwrap_or_default())?),
+        None => None,
+    };
+
+    initialize_client_with_address(local_address)
+}
+
+/// Builds a client whose connections originate from `local_address`, if given.
+pub fn initialize_client_with_address(local_address: Option<IpAddr>) -> Result<Client<HttpsConnector<HttpConnector>, Full<Bytes>>, ClientError> {
     let mut http = HttpConnector::new();
     http.enforce_http(false);
-    
-    if let Some(subnet) = subnet {
-        let random_ip = crate::utils::get_rand_ipv6(subnet, subnet_id.unwrap_or_default()).map_err(|e| ClientError::InvalidIp(e.to_string()))?;
-        http.set_local_address(Some(random_ip));
-    }
+    http.set_local_address(local_address);
     
     let tls = TlsConnector::builder()
         .danger_accept_invalid_certs(true)
@@ -73,44 +109,61 @@ pub fn initialize_client(subnet: Option<&str>, subnet_id: Option<u16>) -> Result
     Ok(client)
 }
 
-pub struct InnerTubeRequestFields<'a> {
-    pub authorization: Option<&'a str>,
-    pub cookie: Option<&'a str>,
+#[derive(Clone)]
+pub struct InnerTubeRequestFields {
+    pub authorization: Option<String>,
+    pub cookie: Option<String>,
+    pub client_profile: Option<ClientProfile>,
 }
 
-pub trait InnerTubeRequest<'a> {
-    fn authorization(&mut self) -> &mut Option<&'a str>;
+pub trait InnerTubeRequest {
+    fn authorization(&mut self) -> &mut Option<String>;
+
+    fn cookie(&mut self) -> &mut Option<String>;
 
-    fn cookie(&mut self) -> &mut Option<&'a str>;
+    fn client_profile(&mut self) -> &mut Option<ClientProfile>;
+
+    fn with_authorization(mut self, authorization: &str) -> Self
+    where
+        Self: Sized,
+    {
+        *self.authorization() = Some(authorization.to_string());
+        self
+    }
 
-    fn with_authorization(mut self, authorization: &'a str) -> Self
+    fn with_cookie(mut self, cookie: &str) -> Self
     where
         Self: Sized,
     {
-        *self.authorization() = Some(authorization);
+        *self.cookie() = Some(cookie.to_string());
         self
     }
 
-    fn with_cookie(mut self, cookie: &'a str) -> Self
+    /// Overrides the client's default profile for this request only.
+    fn with_client_profile(mut self, client_profile: ClientProfile) -> Self
     where
         Self: Sized,
     {
-        *self.cookie() = Some(cookie);
+        *self.client_profile() = Some(client_profile);
         self
     }
 }
 
-impl<'a, T> InnerTubeRequest<'a> for T
+impl<T> InnerTubeRequest for T
 where
-    T: AsMut<InnerTubeRequestFields<'a>>,
+    T: AsMut<InnerTubeRequestFields>,
 {
-    fn authorization(&mut self) -> &mut Option<&'a str> {
+    fn authorization(&mut self) -> &mut Option<String> {
         &mut self.as_mut().authorization
     }
 
-    fn cookie(&mut self) -> &mut Option<&'a str> {
+    fn cookie(&mut self) -> &mut Option<String> {
         &mut self.as_mut().cookie
     }
+
+    fn client_profile(&mut self) -> &mut Option<ClientProfile> {
+        &mut self.as_mut().client_profile
+    }
 }
 
 #[derive(Error, Debug)]
@@ -137,227 +190,581 @@ pub enum YouTubeError {
     ProtobufError(#[from] prost::DecodeError),
     #[error("Other error: {0}")]
     Other(Box<dyn Error + Send + Sync>),
+    #[error("Gave up after {attempts} attempts: {last_error}")]
+    RetriesExhausted { attempts: u32, last_error: Box<YouTubeError> },
+    #[error("Proxy error: {0}")]
+    ProxyError(#[from] ProxyError),
 }
 
-pub struct InnertubeClient {
-    client: Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    subnet: String,
-    ip: String
+/// Entry point for every request.
+///
+/// Cloning is cheap: clones share the transport (and so its connection pool and current
+/// address), while the profile and retry policy can be changed per clone. Requests own a
+/// clone of the client, so they can be moved into spawned tasks.
+pub struct InnertubeClient<T: Transport = HyperTransport> {
+    pub(crate) transport: Arc<T>,
+    pub(crate) ip: String,
+    pub(crate) profile: ClientProfile,
+    retry_policy: RetryPolicy,
+    retry_counter: Arc<RetryCounter>,
+    proxy: Option<Proxy>
 }
 
-impl InnertubeClient {
-    pub async fn new(subnet: Option<&str>, ip: String, subnet_id: Option<u16>) -> Self {
-        let client: Client<HttpsConnector<HttpConnector>, Full<Bytes>> = initialize_client(subnet, subnet_id).unwrap();
+impl<T: Transport> Clone for InnertubeClient<T> {
+    fn clone(&self) -> Self {
         InnertubeClient {
-            client,
-            subnet: subnet.unwrap_or_default().to_string(),
-            ip
+            transport: self.transport.clone(),
+            ip: self.ip.clone(),
+            profile: self.profile.clone(),
+            retry_policy: self.retry_policy.clone(),
+            retry_counter: self.retry_counter.clone(),
+            proxy: self.proxy.clone()
         }
     }
+}
+
+impl InnertubeClient<HyperTransport> {
+    pub async fn new(subnet: Option<&str>, ip: String, subnet_id: Option<u16>) -> Self {
+        let transport = HyperTransport::new(subnet, subnet_id).unwrap();
+        InnertubeClient::from_transport(transport, ip)
+    }
+
+    /// Binds to a random address of `allocator`, `rotate_ipv6` draws new ones from it.
+    pub fn from_allocator(allocator: address::AddressAllocator, ip: String) -> Result<Self, ClientError> {
+        Ok(InnertubeClient::from_transport(HyperTransport::from_allocator(allocator)?, ip))
+    }
 
     pub async fn from_client(client: Client<HttpsConnector<HttpConnector>, Full<Bytes>>, ip: String, subnet: Option<&str>) -> Self {
+        InnertubeClient::from_transport(HyperTransport::from(client).with_subnet(subnet), ip)
+    }
+}
+
+impl<T: Transport> InnertubeClient<T> {
+    pub fn from_transport(transport: T, ip: String) -> Self {
         InnertubeClient {
-            client,
-            subnet: subnet.unwrap_or_default().to_string(),
-            ip
+            transport: Arc::new(transport),
+            ip,
+            profile: ClientProfile::default(),
+            retry_policy: RetryPolicy::none(),
+            retry_counter: Arc::default(),
+            proxy: None
+        }
+    }
+
+    /// Switches to a random address within the configured subnet.
+    ///
+    /// The connector is swapped for every clone of this client at once. Requests already in
+    /// flight finish on the previous address. Returns false if the client has no subnet.
+    pub fn rotate_ipv6(&self) -> bool {
+        self.transport.rotate_ipv6()
+    }
+
+    /// Retries ratelimited and failed requests according to `policy`. Requests are sent once by default.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Requests and attempts made so far, counted over this client and all of its clones.
+    pub fn retry_stats(&self) -> RetryStats {
+        self.retry_counter.stats()
+    }
+
+    /// Sends every request through `proxy`. Each proxy gets its own connection pool, which is
+    /// shared between the clones of this client.
+    pub fn with_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    pub fn proxy(&self) -> Option<&Proxy> {
+        self.proxy.as_ref()
+    }
+
+    /// Sends a request built from `profile`, whose user agent is set as the header as well as in
+    /// the context. App clients are answered based on both.
+    pub(crate) async fn send(&self, mut request: Request<Bytes>, profile: &ClientProfile) -> Result<TransportResponse, YouTubeError> {
+        // A proxy picked by the request itself wins over the client's
+        if let Some(proxy) = &self.proxy {
+            if request.extensions().get::<Proxy>().is_none() {
+                request.extensions_mut().insert(proxy.clone());
+            }
+        }
+
+        if let Some(user_agent) = profile.user_agent.as_deref().and_then(|user_agent| HeaderValue::from_str(user_agent).ok()) {
+            request.headers_mut().entry(USER_AGENT).or_insert(user_agent);
+        }
+
+        self.retry_policy.send(self.transport.as_ref(), request, &self.retry_counter).await
+    }
+
+    /// Sets the profile used by every request that isn't given its own.
+    pub fn with_client_profile(mut self, profile: ClientProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    pub fn client_profile(&self) -> &ClientProfile {
+        &self.profile
+    }
+
+    // The request's own profile, or the client's
+    pub(crate) fn profile<'a>(&'a self, fields: &'a InnerTubeRequestFields) -> &'a ClientProfile {
+        fields.client_profile.as_ref().unwrap_or(&self.profile)
+    }
+
+    // For endpoints that only answer to one client, unless the request overrides it
+    pub(crate) fn profile_as(&self, fields: &InnerTubeRequestFields, client_name: ClientName) -> ClientProfile {
+        match &fields.client_profile {
+            Some(profile) => profile.clone(),
+            None => self.profile.as_client(client_name),
         }
     }
 
-    pub fn rotate_ipv6(&mut self) {
-        let random_u16: u16 = rand::thread_rng().gen();
-        self.client = initialize_client(Some(&self.subnet), Some(random_u16)).unwrap();
+    // For endpoints whose statuses are only told apart by their English text, whatever the request's locale
+    pub(crate) fn profile_en(&self, fields: &InnerTubeRequestFields) -> ClientProfile {
+        self.profile(fields).in_english()
+    }
+
+    pub(crate) fn context(&self, fields: &InnerTubeRequestFields) -> youtube::Context {
+        self.profile(fields).context()
     }
 
-    pub fn get_channel(&mut self, channel_id: String) -> GetChannelRequest {
+    // Text in responses is rendered in the language of the profile that made the request
+    pub(crate) fn locale(&self, fields: &InnerTubeRequestFields) -> Locale {
+        self.profile(fields).locale()
+    }
+
+    pub fn get_channel(&self, channel_id: String) -> GetChannelRequest<T> {
         GetChannelRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
             fields: InnerTubeRequestFields{
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             channel_id,
         }
     }
 
-    pub fn get_channel_extended<'client, 'channel>(
-        &'client mut self,
-        channel: &'channel mut models::Channel
-    ) -> GetChannelExtendedRequest<'client> 
-    where
-        'channel: 'client
-    {
+    pub fn get_channel_extended<'a>(&self, channel: &'a mut models::Channel) -> GetChannelExtendedRequest<'a, T> {
         GetChannelExtendedRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
             fields: InnerTubeRequestFields {
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             channel,
         }
     }
 
-    pub fn get_videos(&mut self, channel_id: String) -> GetVideosRequest {
+    pub fn get_videos(&self, channel_id: String) -> GetVideosRequest<T> {
         GetVideosRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
             fields: InnerTubeRequestFields{
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             channel_id,
         }
     }
 
-    pub fn get_videos_extended(&mut self, channel_id: String, tab: browse::videos::ChannelTab) -> GetVideosExtendedRequest {
+    pub fn get_videos_extended(&self, channel_id: String, tab: browse::videos::ChannelTab) -> GetVideosExtendedRequest<T> {
         GetVideosExtendedRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
             fields: InnerTubeRequestFields{
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
+            },
+            channel_id,
+            tab,
+        }
+    }
+
+    /// Streams every video of a channel tab, fetching continuation pages as needed.
+    pub fn videos_stream(&self, channel_id: String, tab: browse::videos::ChannelTab) -> GetVideosStreamRequest<T> {
+        GetVideosStreamRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
             },
             channel_id,
             tab,
+            max_items: None,
+            max_pages: None,
+            stop_when: None,
         }
     }
 
-    pub fn get_popular_videos(&mut self, channel_id: String) -> GetPopularVideosRequest {
+    pub fn get_popular_videos(&self, channel_id: String) -> GetPopularVideosRequest<T> {
         GetPopularVideosRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
             fields: InnerTubeRequestFields{
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             channel_id,
         }
     }
 
-    pub fn get_videos_continued(&mut self, continuation_token: String) -> GetVideosContinuationRequest {
+    pub fn get_videos_continued(&self, continuation_token: String) -> GetVideosContinuationRequest<T> {
         GetVideosContinuationRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
             fields: InnerTubeRequestFields{
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             continuation_token,
         }
     }
 
-    pub fn get_videos_extended_continued(&mut self, continuation_token: String) -> GetVideosExtendedContinuationRequest {
+    pub fn get_videos_extended_continued(&self, continuation_token: String) -> GetVideosExtendedContinuationRequest<T> {
         GetVideosExtendedContinuationRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            continuation_token,
+        }
+    }
+
+    pub fn get_channel_tab(&self, channel_id: String, tab: CollectionTab) -> GetChannelTabRequest<T> {
+        GetChannelTabRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            channel_id,
+            tab,
+        }
+    }
+
+    pub fn get_channel_tab_continued(&self, continuation_token: String) -> GetChannelTabContinuationRequest<T> {
+        GetChannelTabContinuationRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            continuation_token,
+        }
+    }
+
+    pub fn get_community_posts(&self, channel_id: String) -> GetCommunityPostsRequest<T> {
+        GetCommunityPostsRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            channel_id,
+        }
+    }
+
+    pub fn get_community_posts_continued(&self, continuation_token: String) -> GetCommunityPostsContinuationRequest<T> {
+        GetCommunityPostsContinuationRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            continuation_token,
+        }
+    }
+
+    pub fn get_playlist(&self, playlist_id: String) -> GetPlaylistRequest<T> {
+        GetPlaylistRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            playlist_id,
+        }
+    }
+
+    pub fn get_playlist_continued(&self, continuation_token: String) -> GetPlaylistContinuationRequest<T> {
+        GetPlaylistContinuationRequest {
+            client: self.clone(),
             fields: InnerTubeRequestFields {
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             continuation_token,
         }
     }
 
-    pub fn resolve_url(&mut self, url: String) -> ResolveUrlRequest {
+    pub fn resolve_url(&self, url: String) -> ResolveUrlRequest<T> {
         ResolveUrlRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
             fields: InnerTubeRequestFields{
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             url,
         }
     }
 
-    pub fn get_watch_next(&mut self, video_id: String) -> GetWatchNextRequest {
+    /// Resolves many handles and URLs at once, see `ResolveManyRequest`.
+    pub fn resolve_many(&self, urls: Vec<String>) -> ResolveManyRequest<T> {
+        ResolveManyRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            urls,
+            concurrency: 8,
+            cache: None,
+        }
+    }
+
+    pub fn get_watch_next(&self, video_id: String) -> GetWatchNextRequest<T> {
         GetWatchNextRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            video_id,
+        }
+    }
+
+    pub fn get_watch_next_continued(&self, continuation_token: String) -> GetWatchNextContinuationRequest<T> {
+        GetWatchNextContinuationRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            continuation_token,
+        }
+    }
+
+    pub fn get_video_info(&self, video_id: String) -> GetVideoInfoRequest<T> {
+        GetVideoInfoRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            video_id,
+        }
+    }
+
+    pub fn get_video(&self, video_id: String) -> GetVideoRequest<T> {
+        GetVideoRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            video_id,
+        }
+    }
+
+    /// Looks up the exact publish time of each video, see `GetPublishTimesRequest`.
+    pub fn resolve_publish_times<'a>(&self, videos: &'a mut [models::Video]) -> GetPublishTimesRequest<'a, T> {
+        GetPublishTimesRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            videos,
+            concurrency: 8,
+        }
+    }
+
+    pub fn get_comments(&self, video_id: String) -> GetCommentsRequest<T> {
+        GetCommentsRequest {
+            client: self.clone(),
             fields: InnerTubeRequestFields {
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             video_id,
+            sort: CommentSort::Top,
+        }
+    }
+
+    pub fn get_comments_continued(&self, continuation_token: String) -> GetCommentsContinuationRequest<T> {
+        GetCommentsContinuationRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            continuation_token,
+        }
+    }
+
+    pub fn get_comment_replies(&self, continuation_token: String) -> GetCommentsContinuationRequest<T> {
+        GetCommentsContinuationRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            continuation_token,
+        }
+    }
+
+    pub fn search(&self, query: String) -> SearchRequest<T> {
+        SearchRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            query,
+            search_type: None,
+            upload_date: None,
+            duration: None,
+            sort_by: None,
+        }
+    }
+
+    pub fn search_continued(&self, continuation_token: String) -> SearchContinuationRequest<T> {
+        SearchContinuationRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
+            continuation_token,
         }
     }
 
     // Add inside impl InnertubeClient:
-    pub fn has_public_subscriptions(&mut self, channel_id: String) -> HasPublicSubscriptionsRequest {
+    pub fn has_public_subscriptions(&self, channel_id: String) -> HasPublicSubscriptionsRequest<T> {
         HasPublicSubscriptionsRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
             fields: InnerTubeRequestFields {
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             channel_id,
         }
     }
 
     // Authentication required
-    pub fn get_creator_channels(&mut self, channel_ids: Vec<String>) -> GetCreatorChannelsRequest {
+    pub fn get_creator_channels(&self, channel_ids: Vec<String>) -> GetCreatorChannelsRequest<T> {
         GetCreatorChannelsRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
             fields: InnerTubeRequestFields{
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             channel_ids,
         }
     }
 
     // Add this method to the impl InnertubeClient block
-    pub fn search_public_creator_entities(&mut self, query: String) -> SearchPublicCreatorEntitiesRequest {
+    pub fn search_public_creator_entities(&self, query: String) -> SearchPublicCreatorEntitiesRequest<T> {
         SearchPublicCreatorEntitiesRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
             fields: InnerTubeRequestFields {
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             query,
         }
     }
 
-    pub fn update_hide_user_status(&mut self, channel_id: String, hide_user: bool) -> UpdateHideUserStatusRequest {
+    pub fn update_hide_user_status(&self, channel_id: String, hide_user: bool) -> UpdateHideUserStatusRequest<T> {
         UpdateHideUserStatusRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
             fields: InnerTubeRequestFields {
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             channel_id,
             hide_user,
         }
     }
 
-    pub fn get_hidden_users(&mut self, channel_id: String) -> GetHiddenUsersRequest {
+    pub fn get_hidden_users(&self, channel_id: String) -> GetHiddenUsersRequest<T> {
         GetHiddenUsersRequest {
-            client: &mut self.client,
-            ip: &self.ip,
+            client: self.clone(),
             fields: InnerTubeRequestFields {
                 authorization: None,
-                cookie: None
+                cookie: None,
+                client_profile: None
             },
             channel_id,
         }
     }
 
-    pub fn resolve_conditional_redirect(&self, proxy_url: String, channel_id: String) -> ResolveConditionalRedirectRequest {
+    pub fn resolve_conditional_redirect(&self, proxy_url: String, channel_id: String) -> ResolveConditionalRedirectRequest<T> {
         ResolveConditionalRedirectRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
             proxy_url,
             channel_id,
         }
     }
 
-    pub fn detect_country_code(&self, proxy_url: String) -> DetectCountryCodeRequest {
+    pub fn detect_country_code(&self, proxy_url: String) -> DetectCountryCodeRequest<T> {
         DetectCountryCodeRequest {
+            client: self.clone(),
+            fields: InnerTubeRequestFields {
+                authorization: None,
+                cookie: None,
+                client_profile: None
+            },
             proxy_url,
         }
     }
 
+    /// Checks channels and videos from the country of each proxy, see `GeoSweep`.
+    pub fn geo_sweep(&self, proxy_urls: Vec<String>) -> geo::GeoSweep<T> {
+        geo::GeoSweep::new(self.clone(), proxy_urls)
+    }
+
+    /// Follows watch next recommendations from the seed videos, see `RecommendationCrawler`.
+    pub fn crawl_recommendations(&self, seeds: Vec<String>) -> next::crawler::RecommendationCrawler<T> {
+        next::crawler::RecommendationCrawler::new(self.clone(), seeds)
+    }
+
 }
\ No newline at end of file
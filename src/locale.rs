@@ -0,0 +1,371 @@
+use chrono::NaiveDate;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Language of the text YouTube renders in responses, selected from the client profile's `hl`.
+///
+/// Counts ("1,2 Mio. Aufrufe"), dates ("19 févr. 2012") and relative times ("vor 2 Tagen")
+/// are all localized, so they have to be parsed with the language they were rendered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Fr,
+    Es,
+    Pt,
+    Ja,
+    Ko,
+    Ru,
+    Hi,
+}
+
+/// Unit a relative time was given in, which is also how precise the parsed timestamp is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl TimeUnit {
+    pub fn seconds(self) -> i64 {
+        match self {
+            TimeUnit::Second => 1,
+            TimeUnit::Minute => 60,
+            TimeUnit::Hour => 3600,
+            TimeUnit::Day => 86400,
+            TimeUnit::Week => 604800,
+            TimeUnit::Month => 2629746, // ~30.44 days per month
+            TimeUnit::Year => 31556952, // ~365.25 days per year
+        }
+    }
+}
+
+// Compact number suffixes. Suffixes sharing a prefix are listed longest first.
+const EN_MULTIPLIERS: &[(&str, i64)] = &[("K", 1_000), ("M", 1_000_000), ("B", 1_000_000_000)];
+const DE_MULTIPLIERS: &[(&str, i64)] = &[("Tsd.", 1_000), ("Mio.", 1_000_000), ("Mrd.", 1_000_000_000)];
+const FR_MULTIPLIERS: &[(&str, i64)] = &[("k", 1_000), ("Md", 1_000_000_000), ("M", 1_000_000)];
+const ES_MULTIPLIERS: &[(&str, i64)] = &[("mil M", 1_000_000_000), ("mil", 1_000), ("k", 1_000), ("M", 1_000_000)];
+const PT_MULTIPLIERS: &[(&str, i64)] = &[("mil", 1_000), ("mi", 1_000_000), ("bi", 1_000_000_000)];
+const JA_MULTIPLIERS: &[(&str, i64)] = &[("千", 1_000), ("万", 10_000), ("億", 100_000_000)];
+const KO_MULTIPLIERS: &[(&str, i64)] = &[("천", 1_000), ("만", 10_000), ("억", 100_000_000)];
+const RU_MULTIPLIERS: &[(&str, i64)] = &[("тыс", 1_000), ("млрд", 1_000_000_000), ("млн", 1_000_000)];
+// Nukta letters show up both precomposed and decomposed
+const HI_MULTIPLIERS: &[(&str, i64)] = &[
+    ("हज़ार", 1_000), ("ह\u{095B}ार", 1_000), ("हजार", 1_000),
+    ("लाख", 100_000),
+    ("करोड़", 10_000_000), ("करो\u{095C}", 10_000_000),
+];
+
+// Lowercase prefixes of the unit following the amount, e.g. "2 Tagen", "3 か月前", "5 дней"
+const EN_UNITS: &[(&str, TimeUnit)] = &[
+    ("second", TimeUnit::Second), ("minute", TimeUnit::Minute), ("hour", TimeUnit::Hour), ("day", TimeUnit::Day),
+    ("week", TimeUnit::Week), ("month", TimeUnit::Month), ("year", TimeUnit::Year),
+];
+const DE_UNITS: &[(&str, TimeUnit)] = &[
+    ("sekunde", TimeUnit::Second), ("minute", TimeUnit::Minute), ("stunde", TimeUnit::Hour), ("tag", TimeUnit::Day),
+    ("woche", TimeUnit::Week), ("monat", TimeUnit::Month), ("jahr", TimeUnit::Year),
+];
+const FR_UNITS: &[(&str, TimeUnit)] = &[
+    ("seconde", TimeUnit::Second), ("minute", TimeUnit::Minute), ("heure", TimeUnit::Hour), ("jour", TimeUnit::Day),
+    ("semaine", TimeUnit::Week), ("mois", TimeUnit::Month), ("an", TimeUnit::Year),
+];
+const ES_UNITS: &[(&str, TimeUnit)] = &[
+    ("segundo", TimeUnit::Second), ("minuto", TimeUnit::Minute), ("hora", TimeUnit::Hour), ("día", TimeUnit::Day),
+    ("dia", TimeUnit::Day), ("semana", TimeUnit::Week), ("mes", TimeUnit::Month), ("año", TimeUnit::Year),
+    ("ano", TimeUnit::Year),
+];
+const PT_UNITS: &[(&str, TimeUnit)] = &[
+    ("segundo", TimeUnit::Second), ("minuto", TimeUnit::Minute), ("hora", TimeUnit::Hour), ("dia", TimeUnit::Day),
+    ("semana", TimeUnit::Week), ("mês", TimeUnit::Month), ("mes", TimeUnit::Month), ("ano", TimeUnit::Year),
+];
+const JA_UNITS: &[(&str, TimeUnit)] = &[
+    ("秒", TimeUnit::Second), ("分", TimeUnit::Minute), ("時間", TimeUnit::Hour), ("日", TimeUnit::Day),
+    ("週間", TimeUnit::Week), ("か月", TimeUnit::Month), ("ヶ月", TimeUnit::Month), ("カ月", TimeUnit::Month),
+    ("年", TimeUnit::Year),
+];
+const KO_UNITS: &[(&str, TimeUnit)] = &[
+    ("초", TimeUnit::Second), ("분", TimeUnit::Minute), ("시간", TimeUnit::Hour), ("일", TimeUnit::Day),
+    ("주", TimeUnit::Week), ("개월", TimeUnit::Month), ("달", TimeUnit::Month), ("년", TimeUnit::Year),
+];
+const RU_UNITS: &[(&str, TimeUnit)] = &[
+    ("секунд", TimeUnit::Second), ("минут", TimeUnit::Minute), ("час", TimeUnit::Hour), ("дн", TimeUnit::Day),
+    ("день", TimeUnit::Day), ("недел", TimeUnit::Week), ("месяц", TimeUnit::Month), ("год", TimeUnit::Year),
+    ("лет", TimeUnit::Year),
+];
+const HI_UNITS: &[(&str, TimeUnit)] = &[
+    ("सेकंड", TimeUnit::Second), ("मिनट", TimeUnit::Minute), ("घंट", TimeUnit::Hour), ("दिन", TimeUnit::Day),
+    ("हफ", TimeUnit::Week), ("ह\u{095E}", TimeUnit::Week), ("सप्ताह", TimeUnit::Week), ("महीन", TimeUnit::Month),
+    ("साल", TimeUnit::Year), ("वर्ष", TimeUnit::Year),
+];
+
+// "Updated today" / "Updated yesterday" and their translations
+const EN_DAYS: (&[&str], &[&str]) = (&["today"], &["yesterday"]);
+const DE_DAYS: (&[&str], &[&str]) = (&["heute"], &["gestern"]);
+const FR_DAYS: (&[&str], &[&str]) = (&["aujourd'hui", "aujourd’hui"], &["hier"]);
+const ES_DAYS: (&[&str], &[&str]) = (&["hoy"], &["ayer"]);
+const PT_DAYS: (&[&str], &[&str]) = (&["hoje"], &["ontem"]);
+const JA_DAYS: (&[&str], &[&str]) = (&["今日"], &["昨日"]);
+const KO_DAYS: (&[&str], &[&str]) = (&["오늘"], &["어제"]);
+const RU_DAYS: (&[&str], &[&str]) = (&["сегодня"], &["вчера"]);
+const HI_DAYS: (&[&str], &[&str]) = (&["आज"], &["कल"]);
+
+// Lowercase month prefixes, January first. Japanese and Korean dates are numeric.
+const EN_MONTHS: &[&[&str]] = &[
+    &["jan"], &["feb"], &["mar"], &["apr"], &["may"], &["jun"],
+    &["jul"], &["aug"], &["sep"], &["oct"], &["nov"], &["dec"],
+];
+const DE_MONTHS: &[&[&str]] = &[
+    &["jan"], &["feb"], &["mär", "mar"], &["apr"], &["mai"], &["jun"],
+    &["jul"], &["aug"], &["sep"], &["okt"], &["nov"], &["dez"],
+];
+const FR_MONTHS: &[&[&str]] = &[
+    &["janv"], &["févr", "fevr"], &["mars"], &["avr"], &["mai"], &["juin"],
+    &["juil"], &["août", "aout"], &["sept"], &["oct"], &["nov"], &["déc", "dec"],
+];
+const ES_MONTHS: &[&[&str]] = &[
+    &["ene"], &["feb"], &["mar"], &["abr"], &["may"], &["jun"],
+    &["jul"], &["ago"], &["sep"], &["oct"], &["nov"], &["dic"],
+];
+const PT_MONTHS: &[&[&str]] = &[
+    &["jan"], &["fev"], &["mar"], &["abr"], &["mai"], &["jun"],
+    &["jul"], &["ago"], &["set"], &["out"], &["nov"], &["dez"],
+];
+const RU_MONTHS: &[&[&str]] = &[
+    &["янв"], &["фев"], &["мар"], &["апр"], &["мая", "май"], &["июн"],
+    &["июл"], &["авг"], &["сен"], &["окт"], &["ноя"], &["дек"],
+];
+const HI_MONTHS: &[&[&str]] = &[
+    &["जन"], &["फ़र", "\u{095E}र", "फर"], &["मार्च"], &["अप्रै"], &["मई"], &["जून"],
+    &["जुला"], &["अग"], &["सित"], &["अक्तू", "अक्टू"], &["नव"], &["दिस"],
+];
+
+impl Locale {
+    /// Picks the locale from an `hl` value such as "de", "pt-BR" or "es-419".
+    /// Unsupported languages fall back to English.
+    pub fn from_hl(hl: &str) -> Locale {
+        let language = hl.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+
+        match language.as_str() {
+            "de" => Locale::De,
+            "fr" => Locale::Fr,
+            "es" => Locale::Es,
+            "pt" => Locale::Pt,
+            "ja" => Locale::Ja,
+            "ko" => Locale::Ko,
+            "ru" => Locale::Ru,
+            "hi" => Locale::Hi,
+            _ => Locale::En,
+        }
+    }
+
+    fn decimal_separator(self) -> char {
+        match self {
+            Locale::En | Locale::Ja | Locale::Ko | Locale::Hi => '.',
+            Locale::De | Locale::Fr | Locale::Es | Locale::Pt | Locale::Ru => ',',
+        }
+    }
+
+    fn multipliers(self) -> &'static [(&'static str, i64)] {
+        match self {
+            Locale::En => EN_MULTIPLIERS,
+            Locale::De => DE_MULTIPLIERS,
+            Locale::Fr => FR_MULTIPLIERS,
+            Locale::Es => ES_MULTIPLIERS,
+            Locale::Pt => PT_MULTIPLIERS,
+            Locale::Ja => JA_MULTIPLIERS,
+            Locale::Ko => KO_MULTIPLIERS,
+            Locale::Ru => RU_MULTIPLIERS,
+            Locale::Hi => HI_MULTIPLIERS,
+        }
+    }
+
+    fn time_units(self) -> &'static [(&'static str, TimeUnit)] {
+        match self {
+            Locale::En => EN_UNITS,
+            Locale::De => DE_UNITS,
+            Locale::Fr => FR_UNITS,
+            Locale::Es => ES_UNITS,
+            Locale::Pt => PT_UNITS,
+            Locale::Ja => JA_UNITS,
+            Locale::Ko => KO_UNITS,
+            Locale::Ru => RU_UNITS,
+            Locale::Hi => HI_UNITS,
+        }
+    }
+
+    fn relative_days(self) -> (&'static [&'static str], &'static [&'static str]) {
+        match self {
+            Locale::En => EN_DAYS,
+            Locale::De => DE_DAYS,
+            Locale::Fr => FR_DAYS,
+            Locale::Es => ES_DAYS,
+            Locale::Pt => PT_DAYS,
+            Locale::Ja => JA_DAYS,
+            Locale::Ko => KO_DAYS,
+            Locale::Ru => RU_DAYS,
+            Locale::Hi => HI_DAYS,
+        }
+    }
+
+    fn months(self) -> &'static [&'static [&'static str]] {
+        match self {
+            Locale::En => EN_MONTHS,
+            Locale::De => DE_MONTHS,
+            Locale::Fr => FR_MONTHS,
+            Locale::Es => ES_MONTHS,
+            Locale::Pt => PT_MONTHS,
+            Locale::Ru => RU_MONTHS,
+            Locale::Hi => HI_MONTHS,
+            Locale::Ja | Locale::Ko => &[],
+        }
+    }
+
+    /// Parses the first count in a text, exact or compact, e.g. "1,204 videos", "324M subscribers",
+    /// "1,2 Mio. Aufrufe", "1.2万 回視聴" or "조회수 3.4만회". Returns `None` if there is no number.
+    pub fn parse_count(self, text: &str) -> Option<i64> {
+        let start = text.find(|c: char| c.is_ascii_digit())?;
+        let text = &text[start..];
+        let decimal_separator = self.decimal_separator();
+
+        let mut number = String::new();
+        let mut end = text.len();
+        let mut chars = text.char_indices().peekable();
+
+        while let Some((offset, c)) = chars.next() {
+            let next_is_digit = chars.peek().is_some_and(|(_, next)| next.is_ascii_digit());
+
+            if c.is_ascii_digit() {
+                number.push(c);
+            } else if c == decimal_separator && next_is_digit {
+                number.push('.');
+            } else if matches!(c, ',' | '.' | ' ' | '\'' | '\u{a0}' | '\u{202f}') && next_is_digit {
+                // Thousands separator
+            } else {
+                end = offset;
+                break;
+            }
+        }
+
+        let rest = text[end..].trim_start();
+
+        // English suffixes are also used by some localized layouts
+        let multiplier = self.multipliers()
+            .iter()
+            .chain(EN_MULTIPLIERS)
+            .find(|(suffix, _)| {
+                rest.strip_prefix(suffix).is_some_and(|after| {
+                    // "M" must not match the start of a word such as "Mitglieder"
+                    let needs_boundary = suffix.chars().last().is_some_and(|c| c.is_alphabetic() && (c as u32) < 0x0900);
+                    !needs_boundary || !after.starts_with(char::is_alphabetic)
+                })
+            })
+            .map(|(_, multiplier)| *multiplier)
+            .unwrap_or(1);
+
+        if number.contains('.') {
+            let value: f64 = number.parse().ok()?;
+            Some((value * multiplier as f64).round() as i64)
+        } else {
+            number.parse::<i64>().ok().map(|value| value * multiplier)
+        }
+    }
+
+    /// Parses a relative time such as "2 days ago", "Streamed 3 weeks ago", "vor 2 Tagen" or
+    /// "Updated yesterday" into an approximate unix timestamp.
+    pub fn parse_relative_time(self, text: &str) -> Option<i64> {
+        self.parse_relative_time_with_unit(text).map(|(timestamp, _)| timestamp)
+    }
+
+    /// Like `parse_relative_time`, for comments and posts whose time can end in a localized
+    /// "(edited)", e.g. "2 days ago (edited)" or "vor 2 Tagen (bearbeitet)". Also returns whether
+    /// it was edited.
+    pub fn parse_relative_time_edited(self, text: &str) -> (Option<i64>, bool) {
+        match text.trim().strip_suffix(')').and_then(|time| time.rsplit_once('(')) {
+            Some((time, _)) => (self.parse_relative_time(time.trim()), true),
+            None => (self.parse_relative_time(text.trim()), false),
+        }
+    }
+
+    /// Like `parse_relative_time`, but also returns the unit the text was given in.
+    /// "3 weeks ago" is only accurate to about a week, "1 year ago" to about a year.
+    pub fn parse_relative_time_with_unit(self, text: &str) -> Option<(i64, TimeUnit)> {
+        let current_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_secs() as i64;
+
+        let (seconds, unit) = self.parse_relative_seconds(text)?;
+        Some((current_time - seconds, unit))
+    }
+
+    fn parse_relative_seconds(self, text: &str) -> Option<(i64, TimeUnit)> {
+        let text = text.to_lowercase();
+
+        let Some(start) = text.find(|c: char| c.is_ascii_digit()) else {
+            let (today, yesterday) = self.relative_days();
+            return if today.iter().any(|word| text.contains(word)) {
+                Some((0, TimeUnit::Day))
+            } else if yesterday.iter().any(|word| text.contains(word)) {
+                Some((TimeUnit::Day.seconds(), TimeUnit::Day))
+            } else {
+                None
+            };
+        };
+
+        let text = &text[start..];
+        let digits_end = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+        let amount: i64 = text[..digits_end].parse().ok()?;
+        let unit_text = text[digits_end..].trim_start();
+
+        self.time_units()
+            .iter()
+            .find(|(prefix, _)| unit_text.starts_with(prefix))
+            .map(|(_, unit)| (amount * unit.seconds(), *unit))
+    }
+
+    /// Parses a date such as "Joined Feb 19, 2012", "Beigetreten am 19.02.2012",
+    /// "Inscrit le 19 févr. 2012" or "2012/02/19 に登録" into a unix timestamp at midnight UTC.
+    pub fn parse_date(self, text: &str) -> Option<i64> {
+        let text = text.to_lowercase();
+
+        let numbers: Vec<u32> = text
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|part| !part.is_empty())
+            .filter_map(|part| part.parse().ok())
+            .collect();
+
+        let month = text
+            .split(|c: char| c.is_whitespace() || c.is_ascii_punctuation() || c.is_ascii_digit())
+            .filter(|word| !word.is_empty())
+            .find_map(|word| self.months()
+                .iter()
+                .position(|prefixes| prefixes.iter().any(|prefix| word.starts_with(prefix))))
+            .map(|index| index as u32 + 1);
+
+        let (year, month, day) = match month {
+            Some(month) => {
+                let year = numbers.iter().find(|number| **number >= 1000)?;
+                let day = numbers.iter().find(|number| (1..=31).contains(*number))?;
+                (*year, month, *day)
+            }
+            None => match numbers[..] {
+                // 2012/02/19, 2012. 2. 19., 2012年2月19日
+                [year, month, day, ..] if year >= 1000 => (year, month, day),
+                // 2/19/2012
+                [month, day, year, ..] if self == Locale::En => (year, month, day),
+                // 19.02.2012
+                [day, month, year, ..] => (year, month, day),
+                _ => return None,
+            },
+        };
+
+        NaiveDate::from_ymd_opt(year as i32, month, day)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|date_time| date_time.and_utc().timestamp())
+    }
+}
@@ -1,5 +1,6 @@
 
 use scylla::frame::value::CqlTimestamp;
+use crate::locale::TimeUnit;
 
 #[derive(Debug, Clone)]
 pub struct Link {
@@ -19,6 +20,26 @@ pub struct ContentOwnerAssociation {
     pub default_channel: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminationReason {
+    Spam,
+    CommunityGuidelines,
+    Copyright,
+    LegalComplaint,
+    AssociatedAccount,
+    TermsOfService,
+    Other(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChannelStatus {
+    Active,
+    Hidden,
+    Deleted,
+    Terminated { reason: TerminationReason },
+    Unknown(String),
+}
+
 #[derive(Debug, Clone)]
 pub struct Channel {
     pub user_id: String,
@@ -38,10 +59,7 @@ pub struct Channel {
     pub has_business_email: bool,
     pub links: Vec<Link>,
     pub tags: Vec<String>,
-    pub deleted: bool,
-    pub hidden: bool,
-    pub terminated: bool,
-    pub termination_reason: String,
+    pub status: ChannelStatus,
     pub no_index: bool,
     pub unlisted: bool,
     pub family_safe: bool,
@@ -57,12 +75,36 @@ pub struct Comment {
     pub comment_id: String,
     pub comment_text: String,
     pub created_at: i64,
+    /// Empty for comments loaded through a continuation token the video can't be read from
     pub video_id: String,
     pub likes: i32,
     pub replies: i32,
     pub edited: bool,
 }
 
+#[derive(Debug, Clone)]
+pub struct CommentThread {
+    pub comment: Comment,
+    // Pass to get_comment_replies to load the replies
+    pub replies_continuation: Option<String>,
+}
+
+/// How far `Video.approx_published_time` can be trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublishedTimePrecision {
+    /// Parsed from relative text such as "3 weeks ago", only accurate to about one of the unit
+    Approximate(TimeUnit),
+    /// From the player microformat. Older videos only carry a date (`Day`), newer ones a full
+    /// timestamp (`Second`).
+    Exact(TimeUnit),
+}
+
+impl PublishedTimePrecision {
+    pub fn is_exact(&self) -> bool {
+        matches!(self, PublishedTimePrecision::Exact(_))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Video {
     pub video_id: String,
@@ -70,11 +112,180 @@ pub struct Video {
     pub hidden_view_count: bool,
     pub badge: Option<String>,
     pub length_seconds: Option<i32>,
-    pub approx_published_time: Option<i64>
+    pub approx_published_time: Option<i64>,
+    /// `None` when there's no publish time
+    pub published_time_precision: Option<PublishedTimePrecision>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemovalReason {
+    TermsOfService,
+    CommunityGuidelines,
+    Copyright,
+    AccountTerminated,
+    Uploader,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayabilityStatus {
+    Ok,
+    Unavailable,
+    Private,
+    Removed(RemovalReason),
+    AgeRestricted,
+    LoginRequired,
+    ContentWarning,
+    GeoBlocked,
+    LiveStreamOffline,
+    Unknown(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct VideoDetails {
+    pub video_id: String,
+    pub title: String,
+    pub description: String,
+    pub user_id: String,
+    pub author: String,
+    pub views: i64,
+    pub length_seconds: i32,
+    pub keywords: Vec<String>,
+    pub category: Option<String>,
+    pub published_at: Option<i64>,
+    pub uploaded_at: Option<i64>,
+    pub is_live: bool,
+    pub is_live_content: bool,
+    pub is_private: bool,
+    pub is_unlisted: bool,
+    pub age_restricted: bool,
+    pub playability: PlayabilityStatus,
+    pub available_countries: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchNextKind {
+    Video,
+    /// Playlist generated for the current video
+    Mix,
+    Playlist,
 }
 
 #[derive(Debug, Clone)]
 pub struct WatchNext {
+    /// Empty for mixes, which have no owner
+    pub user_id: String,
+    /// First video of the playlist for mixes and playlists
+    pub video_id: String,
+    pub kind: WatchNextKind,
+    pub playlist_id: Option<String>,
+    pub title: String,
+    /// `None` when hidden, and for mixes and playlists
+    pub views: Option<i64>,
+    pub length_seconds: Option<i32>,
+    /// e.g. "3 weeks ago"
+    pub published_text: Option<String>,
+    pub approx_published_time: Option<i64>,
+    /// e.g. "New", "4K" or "LIVE"
+    pub badges: Vec<String>,
+    pub video_count: Option<i32>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    pub title: String,
+    pub start_seconds: i32
+}
+
+/// What the watch page shows below the player.
+#[derive(Debug, Clone)]
+pub struct VideoInfo {
+    pub video_id: String,
+    pub title: String,
+    /// Exact, e.g. from "1,234,567 views". Concurrent viewers for live streams.
+    pub views: Option<i64>,
+    /// `None` when likes are hidden
+    pub likes: Option<i64>,
+    pub user_id: String,
+    pub author: String,
+    /// e.g. "324M subscribers", `None` when hidden
+    pub subscriber_text: Option<String>,
+    pub subscribers: Option<i64>,
+    pub description: String,
+    /// Lines of the description with a timestamp, e.g. "2:15 The glass bridge"
+    pub description_timestamps: Vec<Chapter>,
+    /// From the player's progress bar, or the description timestamps if they start at 0:00
+    pub chapters: Vec<Chapter>,
+    pub hashtags: Vec<String>,
+    /// e.g. "Nov 24, 2021", "Premiered Nov 24, 2021" or "Streamed live on Nov 24, 2021"
+    pub date_text: Option<String>,
+    /// `date_text` at midnight UTC
+    pub published_at: Option<i64>
+}
+
+#[derive(Debug, Clone)]
+pub struct ChannelStub {
+    pub user_id: String,
+    pub display_name: String,
+    pub handle: Option<String>,
+    pub subscribers: Option<i64>,
+    pub verified: bool
+}
+
+#[derive(Debug, Clone)]
+pub struct PlaylistStub {
+    pub playlist_id: String,
+    pub title: String,
+    pub user_id: Option<String>,
+    pub video_count: Option<i32>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaylistVisibility {
+    Public,
+    Unlisted,
+    Private,
+}
+
+#[derive(Debug, Clone)]
+pub struct Playlist {
+    pub playlist_id: String,
+    pub title: String,
+    pub user_id: Option<String>,
+    pub video_count: i32,
+    pub views: i64,
+    pub last_updated: Option<i64>,
+    pub visibility: PlaylistVisibility,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlaylistEntry {
+    // 1-based position in the playlist
+    pub index: i32,
+    pub video: Video,
+}
+
+#[derive(Debug, Clone)]
+pub struct Product {
+    pub title: String,
+    pub price: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum PostAttachment {
+    Video(String),
+    Poll { choices: Vec<String>, total_votes: i64 },
+    // Image URLs, largest thumbnail of each image
+    Images(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct CommunityPost {
+    pub post_id: String,
     pub user_id: String,
-    pub video_id: String
-}
\ No newline at end of file
+    pub content_text: String,
+    pub attachment: Option<PostAttachment>,
+    pub likes: i32,
+    pub created_at: i64,
+    pub edited: bool,
+}
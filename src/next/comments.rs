@@ -0,0 +1,343 @@
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
+use hyper::body::Bytes;
+use hyper::{Method, Request, StatusCode};
+use prost::Message;
+use base64::{Engine as _, engine::general_purpose::{STANDARD, URL_SAFE}};
+use crate::models::{Comment, CommentThread};
+use crate::client_profile::ClientProfile;
+use crate::locale::Locale;
+use serde_json::Value;
+use std::collections::HashMap;
+
+// Comments are only returned as view models backed by entities in `frameworkUpdates`, whose protobuf
+// layout isn't mapped, so these requests ask for JSON responses
+const COMMENT_SECTION_FIELDMASK: &str = "contents.twoColumnWatchNextResults.results.results.contents.itemSectionRenderer(sectionIdentifier,contents.continuationItemRenderer.continuationEndpoint.continuationCommand.token)";
+const CONTINUATION_ITEM_FIELDMASK: &str = "continuationItemRenderer(continuationEndpoint.continuationCommand.token,button.buttonRenderer.command.continuationCommand.token)";
+const COMMENT_RENDERER_FIELDMASK: &str = "commentRenderer(authorEndpoint.browseEndpoint.browseId,contentText.runs.text,publishedTimeText.runs.text,commentId,voteCount.simpleText,replyCount)";
+const COMMENT_ENTITY_FIELDMASK: &str = "frameworkUpdates.entityBatchUpdate.mutations(entityKey,payload.commentEntityPayload(properties(commentId,content.content,publishedTime),author.channelId,toolbar(likeCountNotliked,replyCount)))";
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum CommentSort {
+    #[default]
+    Top,
+    Newest,
+}
+
+impl CommentSort {
+    // Position in the sort menu of the comment section header
+    fn menu_index(self) -> usize {
+        match self {
+            CommentSort::Top => 0,
+            CommentSort::Newest => 1,
+        }
+    }
+}
+
+// Comment and reply continuations share the same envelope, so the video id can be read back out of them
+fn video_id_from_continuation(token: &str) -> Option<String> {
+    let token = urlencoding::decode(token).ok()?;
+    let bytes = URL_SAFE.decode(token.as_bytes())
+        .or_else(|_| STANDARD.decode(token.as_bytes()))
+        .ok()?;
+
+    youtube::CommentsContinuation::decode(bytes.as_slice()).ok()?
+        .video
+        .map(|video| video.video_id)
+        .filter(|video_id| !video_id.is_empty())
+}
+
+// "Show more replies" carries its token in a button instead of the endpoint
+fn continuation_token(renderer: &Value) -> Option<String> {
+    renderer["continuationEndpoint"]["continuationCommand"]["token"].as_str()
+        .or_else(|| renderer["button"]["buttonRenderer"]["command"]["continuationCommand"]["token"].as_str())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_string())
+}
+
+fn runs_text(text: &Value) -> String {
+    text["runs"].as_array()
+        .map(|runs| runs.iter().filter_map(|run| run["text"].as_str()).collect())
+        .unwrap_or_default()
+}
+
+// Counts are sent as text, e.g. "1.2K", and left blank when zero
+fn parse_count(count: &Value, locale: Locale) -> i64 {
+    match count {
+        Value::Number(count) => count.as_i64().unwrap_or(0),
+        Value::String(count) => locale.parse_count(count).unwrap_or(0),
+        _ => 0,
+    }
+}
+
+fn parse_comment_entity(payload: &Value, video_id: &str, locale: Locale) -> Option<Comment> {
+    let properties = &payload["properties"];
+    let comment_id = properties["commentId"].as_str().filter(|id| !id.is_empty())?;
+
+    let (created_at, edited) = locale.parse_relative_time_edited(properties["publishedTime"].as_str().unwrap_or_default());
+
+    Some(Comment {
+        user_id: payload["author"]["channelId"].as_str()
+            .and_then(|channel_id| channel_id.strip_prefix("UC"))
+            .unwrap_or_default()
+            .to_string(),
+        comment_id: comment_id.to_string(),
+        comment_text: properties["content"]["content"].as_str().unwrap_or_default().to_string(),
+        created_at: created_at.unwrap_or_default(),
+        video_id: video_id.to_string(),
+        likes: parse_count(&payload["toolbar"]["likeCountNotliked"], locale) as i32,
+        replies: parse_count(&payload["toolbar"]["replyCount"], locale) as i32,
+        edited
+    })
+}
+
+// Older layout, still returned to some clients
+fn parse_comment_renderer(renderer: &Value, video_id: &str, locale: Locale) -> Option<Comment> {
+    let comment_id = renderer["commentId"].as_str().filter(|id| !id.is_empty())?;
+
+    let (created_at, edited) = locale.parse_relative_time_edited(&runs_text(&renderer["publishedTimeText"]));
+
+    Some(Comment {
+        user_id: renderer["authorEndpoint"]["browseEndpoint"]["browseId"].as_str()
+            .and_then(|browse_id| browse_id.strip_prefix("UC"))
+            .unwrap_or_default()
+            .to_string(),
+        comment_id: comment_id.to_string(),
+        comment_text: runs_text(&renderer["contentText"]),
+        created_at: created_at.unwrap_or_default(),
+        video_id: video_id.to_string(),
+        // No vote count is returned when the comment has no likes
+        likes: renderer["voteCount"]["simpleText"].as_str()
+            .and_then(|count| locale.parse_count(count))
+            .unwrap_or(0) as i32,
+        replies: renderer["replyCount"].as_i64().unwrap_or(0) as i32,
+        edited
+    })
+}
+
+async fn send_next_request<T: Transport>(
+    client: &InnertubeClient<T>,
+    profile: &ClientProfile,
+    request: youtube::NextRequest,
+    fieldmask: String
+) -> Result<Value, YouTubeError> {
+    let mut payload = Vec::new();
+    request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(format!("https://{}/youtubei/v1/next?alt=json", client.ip))
+        .header("Host", "youtubei.googleapis.com")
+        .header("Content-Type", "application/x-protobuf")
+        .header("X-Goog-Fieldmask", fieldmask)
+        .body(Bytes::from(payload))
+        .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+    let resp = client.send(req, profile).await?;
+
+    match resp.status {
+        StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+        StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+        StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+        StatusCode::OK => Ok(()),
+        status => Err(YouTubeError::UnknownStatusCode(status)),
+    }?;
+
+    serde_json::from_slice(&resp.body).map_err(|e| YouTubeError::Other(Box::new(e)))
+}
+
+/// Reads the continuation of the comment section from the watch page. None if comments are
+/// turned off or the video is unavailable.
+async fn comment_section_continuation<T: Transport>(
+    client: &InnertubeClient<T>,
+    profile: &ClientProfile,
+    video_id: &str
+) -> Result<Option<String>, YouTubeError> {
+    let request = youtube::NextRequest {
+        context: Some(profile.context()),
+        video_id: video_id.to_string(),
+        continuation: None,
+    };
+
+    let response = send_next_request(client, profile, request, COMMENT_SECTION_FIELDMASK.to_string()).await?;
+
+    let contents = response["contents"]["twoColumnWatchNextResults"]["results"]["results"]["contents"].as_array();
+    let section = contents.into_iter()
+        .flatten()
+        .map(|content| &content["itemSectionRenderer"])
+        .find(|section| section["sectionIdentifier"] == "comment-item-section");
+
+    Ok(section
+        .and_then(|section| section["contents"].as_array())
+        .and_then(|contents| contents.iter().find_map(|content| continuation_token(&content["continuationItemRenderer"]))))
+}
+
+struct CommentsPage {
+    threads: Vec<CommentThread>,
+    continuation: Option<String>,
+    // Tokens of the header's sort menu, in `CommentSort` order. Only on the first page.
+    sort_continuations: Vec<String>,
+}
+
+async fn send_comments_request<T: Transport>(
+    client: &InnertubeClient<T>,
+    profile: &ClientProfile,
+    video_id: &str,
+    continuation: String
+) -> Result<CommentsPage, YouTubeError> {
+    let locale = profile.locale();
+
+    let request = youtube::NextRequest {
+        context: Some(profile.context()),
+        video_id: String::new(),
+        continuation: Some(continuation),
+    };
+
+    let continuation_items = format!(
+        "continuationItems(commentsHeaderRenderer.sortMenu.sortFilterSubMenuRenderer.subMenuItems.serviceEndpoint.continuationCommand.token,commentThreadRenderer(commentViewModel.commentViewModel.commentKey,comment.{},replies.commentRepliesRenderer.contents.{}),commentViewModel.commentKey,{},{})",
+        COMMENT_RENDERER_FIELDMASK, CONTINUATION_ITEM_FIELDMASK, COMMENT_RENDERER_FIELDMASK, CONTINUATION_ITEM_FIELDMASK
+    );
+    let fieldmask = format!(
+        "onResponseReceivedEndpoints(reloadContinuationItemsCommand.{},appendContinuationItemsAction.{}),{}",
+        continuation_items, continuation_items, COMMENT_ENTITY_FIELDMASK
+    );
+
+    let response = send_next_request(client, profile, request, fieldmask).await?;
+
+    // View models only hold the key of the entity with the actual comment
+    let entities: HashMap<&str, &Value> = response["frameworkUpdates"]["entityBatchUpdate"]["mutations"].as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|mutation| Some((mutation["entityKey"].as_str()?, &mutation["payload"]["commentEntityPayload"])))
+        .filter(|(_, payload)| payload.is_object())
+        .collect();
+    let parse_view_model = |view_model: &Value| view_model["commentKey"].as_str()
+        .and_then(|key| entities.get(key))
+        .and_then(|payload| parse_comment_entity(payload, video_id, locale));
+
+    let items = response["onResponseReceivedEndpoints"].as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|endpoint| {
+            let reload = endpoint["reloadContinuationItemsCommand"]["continuationItems"].as_array();
+            let append = endpoint["appendContinuationItemsAction"]["continuationItems"].as_array();
+            reload.into_iter().chain(append).flatten()
+        });
+
+    let mut page = CommentsPage {
+        threads: Vec::new(),
+        continuation: None,
+        sort_continuations: Vec::new(),
+    };
+
+    for item in items {
+        if let Some(sort_items) = item["commentsHeaderRenderer"]["sortMenu"]["sortFilterSubMenuRenderer"]["subMenuItems"].as_array() {
+            page.sort_continuations = sort_items.iter()
+                .map(|sort_item| sort_item["serviceEndpoint"]["continuationCommand"]["token"].as_str().unwrap_or_default().to_string())
+                .collect();
+        }
+
+        // Top level comments
+        let thread = &item["commentThreadRenderer"];
+        if thread.is_object() {
+            let comment = parse_view_model(&thread["commentViewModel"]["commentViewModel"])
+                .or_else(|| parse_comment_renderer(&thread["comment"]["commentRenderer"], video_id, locale));
+
+            if let Some(comment) = comment {
+                let replies_continuation = thread["replies"]["commentRepliesRenderer"]["contents"].as_array()
+                    .and_then(|contents| contents.iter().find_map(|content| continuation_token(&content["continuationItemRenderer"])));
+
+                page.threads.push(CommentThread {
+                    comment,
+                    replies_continuation
+                });
+            }
+        }
+
+        // Replies
+        let reply = parse_view_model(&item["commentViewModel"])
+            .or_else(|| parse_comment_renderer(&item["commentRenderer"], video_id, locale));
+        if let Some(comment) = reply {
+            page.threads.push(CommentThread {
+                comment,
+                replies_continuation: None
+            });
+        }
+
+        if let Some(token) = continuation_token(&item["continuationItemRenderer"]) {
+            page.continuation = Some(token);
+        }
+    }
+
+    Ok(page)
+}
+
+pub struct GetCommentsRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub video_id: String,
+    pub sort: CommentSort,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetCommentsRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> GetCommentsRequest<T> {
+    pub fn with_sort(mut self, sort: CommentSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Returns no comments if they are turned off.
+    pub async fn send(self) -> Result<(Vec<CommentThread>, Option<String>), YouTubeError> {
+        let profile = self.client.profile(&self.fields);
+
+        let Some(continuation) = comment_section_continuation(&self.client, profile, &self.video_id).await? else {
+            return Ok((Vec::new(), None));
+        };
+
+        let page = send_comments_request(&self.client, profile, &self.video_id, continuation).await?;
+
+        // The section opens with top comments, other orders are loaded through the header's sort menu
+        let page = match self.sort {
+            CommentSort::Top => page,
+            sort => {
+                let continuation = page.sort_continuations.get(sort.menu_index())
+                    .filter(|token| !token.is_empty())
+                    .cloned()
+                    .ok_or_else(|| YouTubeError::ParseError(format!("No {:?} sort option in the comment section of {}", sort, self.video_id)))?;
+                send_comments_request(&self.client, profile, &self.video_id, continuation).await?
+            }
+        };
+
+        Ok((page.threads, page.continuation))
+    }
+}
+
+/// Loads the next page of a comment section, or a page of replies to a comment.
+/// Replies are returned as threads without a `replies_continuation`.
+pub struct GetCommentsContinuationRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub continuation_token: String,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetCommentsContinuationRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> GetCommentsContinuationRequest<T> {
+    /// `Comment::video_id` is left empty if the token doesn't carry it in the layout this crate
+    /// generates, e.g. for tokens taken from the web client.
+    pub async fn send(self) -> Result<(Vec<CommentThread>, Option<String>), YouTubeError> {
+        let video_id = video_id_from_continuation(&self.continuation_token).unwrap_or_default();
+
+        let page = send_comments_request(&self.client, self.client.profile(&self.fields), &video_id, self.continuation_token).await?;
+        Ok((page.threads, page.continuation))
+    }
+}
@@ -0,0 +1,188 @@
+use crate::models::WatchNext;
+use crate::transport::{HyperTransport, Transport};
+use crate::{InnertubeClient, YouTubeError};
+use futures::stream::{self, StreamExt};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// A video recommended on the watch page of another video.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecommendationEdge {
+    pub source_video_id: String,
+    pub video_id: String,
+    /// Channel of the recommended video, without the "UC" prefix
+    pub user_id: String,
+    /// Hops from the seeds to the source video, 0 for the seeds themselves
+    pub depth: usize,
+}
+
+/// A video whose recommendations couldn't be fetched.
+#[derive(Debug)]
+pub struct CrawlError {
+    pub video_id: String,
+    pub error: YouTubeError,
+}
+
+#[derive(Debug)]
+pub struct CrawlReport {
+    /// In the order the source videos were crawled
+    pub edges: Vec<RecommendationEdge>,
+    /// Videos whose recommendations were fetched, in crawl order
+    pub crawled: Vec<String>,
+    /// Everything crawled, including the set the crawler started with. Pass it to `with_visited`
+    /// to continue from where this crawl stopped without revisiting videos.
+    pub visited: HashSet<String>,
+    /// Videos that were queued but not crawled because `max_nodes` was reached, in the order they
+    /// would have been crawled. Use them as the seeds of the next crawl.
+    pub pending: Vec<String>,
+    pub errors: Vec<CrawlError>,
+}
+
+impl CrawlReport {
+    /// Channels that were recommended, with how many edges point to each.
+    pub fn channels(&self) -> BTreeMap<&str, usize> {
+        let mut channels = BTreeMap::new();
+        for edge in &self.edges {
+            *channels.entry(edge.user_id.as_str()).or_insert(0) += 1;
+        }
+        channels
+    }
+}
+
+/// Walks the recommendation graph breadth first, starting from a list of seed videos.
+///
+/// Only videos are followed, mixes and playlists in the sidebar are ignored. Each video is
+/// crawled at most once.
+pub struct RecommendationCrawler<T: Transport = HyperTransport> {
+    client: InnertubeClient<T>,
+    seeds: Vec<String>,
+    max_depth: usize,
+    max_nodes: usize,
+    max_per_channel: Option<usize>,
+    concurrency: usize,
+    visited: HashSet<String>,
+}
+
+impl<T: Transport> RecommendationCrawler<T> {
+    pub fn new(client: InnertubeClient<T>, seeds: Vec<String>) -> Self {
+        RecommendationCrawler {
+            client,
+            seeds,
+            max_depth: 2,
+            max_nodes: 100,
+            max_per_channel: None,
+            concurrency: 8,
+            visited: HashSet::new(),
+        }
+    }
+
+    /// How many hops from the seeds recommendations are followed. 0 only crawls the seeds.
+    /// Defaults to 2.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// How many videos are crawled at most, seeds included. Defaults to 100.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// How many videos of the same channel are followed at most. Recommendations beyond the cap
+    /// still show up as edges, so the channel is discovered, but aren't crawled.
+    pub fn with_max_per_channel(mut self, max_per_channel: usize) -> Self {
+        self.max_per_channel = Some(max_per_channel);
+        self
+    }
+
+    /// How many watch pages are fetched at once. Defaults to 8.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Videos that are neither crawled nor followed, e.g. `CrawlReport::visited` of an earlier crawl.
+    pub fn with_visited(mut self, visited: HashSet<String>) -> Self {
+        self.visited = visited;
+        self
+    }
+
+    pub async fn crawl(self) -> CrawlReport {
+        let mut report = CrawlReport {
+            edges: Vec::new(),
+            crawled: Vec::new(),
+            visited: self.visited,
+            pending: Vec::new(),
+            errors: Vec::new(),
+        };
+        let mut per_channel: HashMap<String, usize> = HashMap::new();
+
+        // Crawled or waiting in a frontier, so no video is queued twice
+        let mut queued = report.visited.clone();
+        let mut frontier: Vec<String> = Vec::new();
+        for seed in self.seeds {
+            if queued.insert(seed.clone()) {
+                frontier.push(seed);
+            }
+        }
+
+        let mut depth = 0;
+        while !frontier.is_empty() && report.crawled.len() < self.max_nodes {
+            let remaining = self.max_nodes - report.crawled.len();
+            if frontier.len() > remaining {
+                report.pending.extend(frontier.drain(remaining..));
+            }
+
+            let client = &self.client;
+            let results: Vec<(String, Result<Vec<WatchNext>, YouTubeError>)> = stream::iter(frontier)
+                .map(|video_id| async move {
+                    let result = client.get_watch_next(video_id.clone()).send().await;
+                    (video_id, result)
+                })
+                .buffered(self.concurrency)
+                .collect()
+                .await;
+
+            let mut next_frontier = Vec::new();
+            for (source_video_id, result) in results {
+                report.visited.insert(source_video_id.clone());
+                report.crawled.push(source_video_id.clone());
+
+                let watch_next = match result {
+                    Ok(watch_next) => watch_next,
+                    Err(error) => {
+                        tracing::warn!("Crawling recommendations of {} failed: {}", source_video_id, error);
+                        report.errors.push(CrawlError { video_id: source_video_id, error });
+                        continue;
+                    }
+                };
+
+                for recommendation in watch_next {
+                    let follow = depth < self.max_depth
+                        && !queued.contains(&recommendation.video_id)
+                        && self.max_per_channel.is_none_or(|max| per_channel.get(&recommendation.user_id).copied().unwrap_or(0) < max);
+
+                    if follow {
+                        queued.insert(recommendation.video_id.clone());
+                        *per_channel.entry(recommendation.user_id.clone()).or_insert(0) += 1;
+                        next_frontier.push(recommendation.video_id.clone());
+                    }
+
+                    report.edges.push(RecommendationEdge {
+                        source_video_id: source_video_id.clone(),
+                        video_id: recommendation.video_id,
+                        user_id: recommendation.user_id,
+                        depth,
+                    });
+                }
+            }
+
+            frontier = next_frontier;
+            depth += 1;
+        }
+
+        // Only left over when the crawl stopped at max_nodes
+        report.pending.extend(frontier);
+        report
+    }
+}
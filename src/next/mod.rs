@@ -1 +1,4 @@
-pub mod watch_next;
\ No newline at end of file
+pub mod watch_next;
+pub mod comments;
+pub mod crawler;
+pub mod video_info;
@@ -0,0 +1,194 @@
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
+use hyper::body::Bytes;
+use hyper::{Method, Request, StatusCode};
+use prost::Message;
+use crate::models::{Chapter, VideoInfo};
+use crate::browse::videos::parse_length_text;
+use crate::next::watch_next::{byline_user_id, text_content};
+use lazy_static::lazy_static;
+use regex::Regex;
+use youtube::next_response::contents::two_column_watch_next_results::results::results::Contents;
+
+const PRIMARY_INFO_FIELDMASK: &str = "videoPrimaryInfoRenderer(title.runs.text,viewCount.videoViewCountRenderer.viewCount.simpleText,videoActions.menuRenderer.topLevelButtons.segmentedLikeDislikeButtonViewModel.likeButtonViewModel.likeButtonViewModel.toggleButtonViewModel.toggleButtonViewModel.defaultButtonViewModel.buttonViewModel.accessibilityText,dateText.simpleText,superTitleLink.runs.text)";
+const SECONDARY_INFO_FIELDMASK: &str = "videoSecondaryInfoRenderer(owner.videoOwnerRenderer(title.runs(text,navigationEndpoint.browseEndpoint.browseId),subscriberCountText.simpleText),attributedDescription.content)";
+const CHAPTERS_FIELDMASK: &str = "playerOverlays.playerOverlayRenderer.decoratedPlayerBarRenderer.decoratedPlayerBarRenderer.playerBar.multiMarkersPlayerBarRenderer.markersMap(key,value.chapters.chapterRenderer(title.simpleText,timeRangeStartMillis))";
+
+lazy_static! {
+    // "2:15", "1:02:15", optionally wrapped in brackets
+    static ref TIMESTAMP_REGEX: Regex = Regex::new(r"(?:^|[\s(\[])(\d{1,2}(?::\d{2}){1,2})(?:$|[\s)\]])").unwrap();
+}
+
+/// Finds the first timestamp on each line of a description, e.g. "0:00 Intro", "Intro - 0:00" or
+/// "(1:02:15) Finale". The rest of the line is used as the title.
+pub fn parse_description_timestamps(description: &str) -> Vec<Chapter> {
+    description.lines()
+        .filter_map(|line| {
+            let timestamp = TIMESTAMP_REGEX.captures(line)?.get(1)?;
+            let start_seconds = parse_length_text(timestamp.as_str())?;
+
+            let title = format!("{} {}", &line[..timestamp.start()], &line[timestamp.end()..]);
+            let title = title.trim_matches(|c: char| c.is_whitespace() || "-–—|:•()[]".contains(c));
+
+            Some(Chapter {
+                title: title.split_whitespace().collect::<Vec<_>>().join(" "),
+                start_seconds,
+            })
+        })
+        .collect()
+}
+
+pub struct GetVideoInfoRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub video_id: String,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetVideoInfoRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> GetVideoInfoRequest<T> {
+    pub async fn send(self) -> Result<VideoInfo, YouTubeError> {
+        let locale = self.client.locale(&self.fields);
+
+        let request = youtube::NextRequest {
+            context: Some(self.client.context(&self.fields)),
+            video_id: self.video_id.clone(),
+            continuation: None,
+        };
+
+        let mut payload = Vec::new();
+        request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/youtubei/v1/next", self.client.ip))
+            .header("Host", "youtubei.googleapis.com")
+            .header("Content-Type", "application/x-protobuf")
+            .header("X-Goog-Fieldmask", format!(
+                "contents.twoColumnWatchNextResults.results.results.contents({},{}),{}",
+                PRIMARY_INFO_FIELDMASK, SECONDARY_INFO_FIELDMASK, CHAPTERS_FIELDMASK
+            ))
+            .body(Bytes::from(payload))
+            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
+
+        match resp.status {
+            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+            StatusCode::OK => Ok(()),
+            status => Err(YouTubeError::UnknownStatusCode(status)),
+        }?;
+
+        let response = youtube::NextResponse::decode(resp.body)?;
+
+        let contents: Vec<Contents> = response.contents
+            .and_then(|contents| contents.two_column_watch_next_results)
+            .and_then(|watch_next_results| watch_next_results.results)
+            .and_then(|results| results.results)
+            .map(|results| results.contents)
+            .unwrap_or_default();
+
+        let mut primary = None;
+        let mut secondary = None;
+        for content in contents {
+            primary = primary.or(content.video_primary_info_renderer);
+            secondary = secondary.or(content.video_secondary_info_renderer);
+        }
+
+        // Unavailable and age restricted videos come back without any info
+        let primary = primary.ok_or(YouTubeError::WatchNextRendererUnavailable)?;
+
+        let views = primary.view_count
+            .and_then(|view_count| view_count.video_view_count_renderer)
+            .and_then(|renderer| text_content(renderer.view_count))
+            .and_then(|text| locale.parse_count(&text));
+
+        // e.g. "like this video along with 12,345,678 other people"
+        let likes = primary.video_actions
+            .and_then(|actions| actions.menu_renderer)
+            .and_then(|menu| menu.top_level_buttons.into_iter()
+                .filter_map(|button| button.segmented_like_dislike_button_view_model)
+                .filter_map(|view_model| view_model.like_button_view_model)
+                .filter_map(|view_model| view_model.like_button_view_model)
+                .filter_map(|view_model| view_model.toggle_button_view_model)
+                .filter_map(|view_model| view_model.toggle_button_view_model)
+                .filter_map(|view_model| view_model.default_button_view_model)
+                .filter_map(|view_model| view_model.button_view_model)
+                .find_map(|button| locale.parse_count(&button.accessibility_text)));
+
+        let hashtags = primary.super_title_link
+            .map(|link| link.runs.into_iter()
+                .map(|run| run.text.trim().to_string())
+                .filter(|text| text.starts_with('#'))
+                .collect())
+            .unwrap_or_default();
+
+        let date_text = text_content(primary.date_text);
+        let published_at = date_text.as_deref().and_then(|text| locale.parse_date(text));
+
+        let owner = secondary.as_ref()
+            .and_then(|secondary| secondary.owner.as_ref())
+            .and_then(|owner| owner.video_owner_renderer.as_ref());
+        let user_id = byline_user_id(owner.and_then(|owner| owner.title.as_ref())).unwrap_or_default();
+        let author = text_content(owner.and_then(|owner| owner.title.clone())).unwrap_or_default();
+        let subscriber_text = text_content(owner.and_then(|owner| owner.subscriber_count_text.clone()));
+        let subscribers = subscriber_text.as_deref().and_then(|text| locale.parse_count(text));
+
+        let description = secondary
+            .and_then(|secondary| secondary.attributed_description)
+            .map(|description| description.content)
+            .unwrap_or_default();
+        let description_timestamps = parse_description_timestamps(&description);
+
+        let markers_map = response.player_overlays
+            .and_then(|overlays| overlays.player_overlay_renderer)
+            .and_then(|renderer| renderer.decorated_player_bar_renderer)
+            .and_then(|renderer| renderer.decorated_player_bar_renderer)
+            .and_then(|renderer| renderer.player_bar)
+            .and_then(|bar| bar.multi_markers_player_bar_renderer)
+            .map(|renderer| renderer.markers_map)
+            .unwrap_or_default();
+
+        // Chapters from the description are preferred over generated ones, other markers aren't chapters
+        let markers = markers_map.iter().find(|marker| marker.key == "DESCRIPTION_CHAPTERS")
+            .or_else(|| markers_map.iter().find(|marker| marker.key == "AUTO_CHAPTERS"));
+        let mut chapters: Vec<Chapter> = markers
+            .and_then(|marker| marker.value.clone())
+            .map(|value| value.chapters.into_iter()
+                .filter_map(|chapter| chapter.chapter_renderer)
+                .map(|chapter| Chapter {
+                    title: text_content(chapter.title).unwrap_or_default(),
+                    start_seconds: (chapter.time_range_start_millis / 1000) as i32,
+                })
+                .collect())
+            .unwrap_or_default();
+
+        // Same rule as the player, a description only has chapters if they start at 0:00
+        if chapters.is_empty() && description_timestamps.first().is_some_and(|first| first.start_seconds == 0) {
+            chapters = description_timestamps.clone();
+        }
+
+        Ok(VideoInfo {
+            video_id: self.video_id,
+            title: text_content(primary.title).unwrap_or_default(),
+            views,
+            likes,
+            user_id,
+            author,
+            subscriber_text,
+            subscribers,
+            description,
+            description_timestamps,
+            chapters,
+            hashtags,
+            date_text,
+            published_at
+        })
+    }
+}
@@ -1,95 +1,384 @@
-use crate::{youtube, InnerTubeRequestFields, YouTubeError};
-use hyper_tls::HttpsConnector;
-use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
 use hyper::body::Bytes;
-use http_body_util::{BodyExt, Full};
 use hyper::{Method, Request, StatusCode};
 use prost::Message;
-use crate::models::WatchNext;
+use crate::models::{WatchNext, WatchNextKind};
+use crate::client_profile::ClientProfile;
+use crate::locale::Locale;
+use crate::browse::videos::{parse_length_text, parse_view_count_text};
+use youtube::next_response::Item;
+use youtube::next_response::item::{CompactPlaylistRenderer, CompactRadioRenderer, CompactVideoRenderer, ContinuationItemRenderer, LockupViewModel, Text};
+use youtube::next_response::item::lockup_view_model::ContentType;
 
-pub struct GetWatchNextRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
-    pub video_id: String,
+const THUMBNAIL_BADGES_FIELDMASK: &str = "overlays.thumbnailOverlayBadgeViewModel.thumbnailBadges.thumbnailBadgeViewModel.text";
+
+fn item_fieldmask() -> String {
+    format!(
+        "compactVideoRenderer(videoId,title.simpleText,shortBylineText.runs.navigationEndpoint.browseEndpoint.browseId,viewCountText.simpleText,lengthText.simpleText,publishedTimeText.simpleText,badges.metadataBadgeRenderer.label),\
+compactRadioRenderer(playlistId,title.simpleText,navigationEndpoint.watchEndpoint.videoId,videoCountShortText.simpleText),\
+compactPlaylistRenderer(playlistId,title.simpleText,navigationEndpoint.watchEndpoint.videoId,shortBylineText.runs.navigationEndpoint.browseEndpoint.browseId,videoCountShortText.simpleText),\
+lockupViewModel(contentId,contentType,metadata.lockupMetadataViewModel(title.content,metadata.contentMetadataViewModel.metadataRows(metadataParts.text(content,commandRuns.onTap.innertubeCommand.browseEndpoint.browseId),badges.badgeViewModel.badgeText)),contentImage(thumbnailViewModel.{},collectionThumbnailViewModel.primaryThumbnail.thumbnailViewModel.{}),rendererContext.commandContext.onTap.innertubeCommand.watchEndpoint.videoId),\
+continuationItemRenderer.continuationEndpoint.continuationCommand.token",
+        THUMBNAIL_BADGES_FIELDMASK, THUMBNAIL_BADGES_FIELDMASK
+    )
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for GetWatchNextRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
-        &mut self.fields
+fn parse_continuation_item(item: ContinuationItemRenderer) -> Option<String> {
+    item.continuation_endpoint
+        .and_then(|endpoint| endpoint.continuation_command)
+        .map(|command| command.token)
+        .filter(|token| !token.is_empty())
+}
+
+pub(crate) fn text_content(text: Option<Text>) -> Option<String> {
+    let text = text?;
+    let content = match text.simple_text.is_empty() {
+        true => text.runs.into_iter().map(|run| run.text).collect::<String>(),
+        false => text.simple_text,
+    };
+    Some(content.trim().to_string()).filter(|content| !content.is_empty())
+}
+
+// Strips the "UC" prefix from the first channel linked in a byline
+pub(crate) fn byline_user_id(text: Option<&Text>) -> Option<String> {
+    text?.runs.iter()
+        .filter_map(|run| run.navigation_endpoint.as_ref())
+        .filter_map(|endpoint| endpoint.browse_endpoint.as_ref())
+        .find_map(|browse| browse.browse_id.strip_prefix("UC").map(|id| id.to_string()))
+}
+
+fn parse_compact_video(video: CompactVideoRenderer, locale: Locale) -> Option<WatchNext> {
+    if video.video_id.is_empty() {
+        return None;
     }
+
+    // Recommendations that don't link their channel aren't videos we can follow
+    let user_id = byline_user_id(video.short_byline_text.as_ref())?;
+
+    let (views, hidden_view_count) = parse_view_count_text(text_content(video.view_count_text).as_deref(), locale);
+    let published_text = text_content(video.published_time_text);
+
+    Some(WatchNext {
+        user_id,
+        video_id: video.video_id,
+        kind: WatchNextKind::Video,
+        playlist_id: None,
+        title: text_content(video.title).unwrap_or_default(),
+        views: (!hidden_view_count).then_some(views),
+        length_seconds: text_content(video.length_text).and_then(|text| parse_length_text(&text)),
+        approx_published_time: published_text.as_deref().and_then(|text| locale.parse_relative_time(text)),
+        published_text,
+        badges: video.badges.into_iter()
+            .filter_map(|badge| badge.metadata_badge_renderer)
+            .map(|renderer| renderer.label)
+            .filter(|label| !label.is_empty())
+            .collect(),
+        video_count: None
+    })
 }
 
-impl<'a> GetWatchNextRequest<'a> {
-    pub async fn send(self) -> Result<Vec<WatchNext>, YouTubeError> {
-        let request = youtube::NextRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { 
-                    client_name: 1, 
-                    client_version: "2.20240614.01.00".to_string() 
-                })
-            }),
-            video_id: self.video_id.clone(),
+fn parse_compact_radio(radio: CompactRadioRenderer, locale: Locale) -> Option<WatchNext> {
+    if radio.playlist_id.is_empty() {
+        return None;
+    }
+
+    Some(WatchNext {
+        user_id: String::new(),
+        video_id: radio.navigation_endpoint
+            .and_then(|endpoint| endpoint.watch_endpoint)
+            .map(|endpoint| endpoint.video_id)
+            .unwrap_or_default(),
+        kind: WatchNextKind::Mix,
+        playlist_id: Some(radio.playlist_id),
+        title: text_content(radio.title).unwrap_or_default(),
+        views: None,
+        length_seconds: None,
+        published_text: None,
+        approx_published_time: None,
+        badges: Vec::new(),
+        // e.g. "50+ videos"
+        video_count: text_content(radio.video_count_short_text)
+            .and_then(|text| locale.parse_count(&text))
+            .map(|count| count as i32)
+    })
+}
+
+fn parse_compact_playlist(playlist: CompactPlaylistRenderer, locale: Locale) -> Option<WatchNext> {
+    if playlist.playlist_id.is_empty() {
+        return None;
+    }
+
+    Some(WatchNext {
+        user_id: byline_user_id(playlist.short_byline_text.as_ref()).unwrap_or_default(),
+        video_id: playlist.navigation_endpoint
+            .and_then(|endpoint| endpoint.watch_endpoint)
+            .map(|endpoint| endpoint.video_id)
+            .unwrap_or_default(),
+        kind: WatchNextKind::Playlist,
+        playlist_id: Some(playlist.playlist_id),
+        title: text_content(playlist.title).unwrap_or_default(),
+        views: None,
+        length_seconds: None,
+        published_text: None,
+        approx_published_time: None,
+        badges: Vec::new(),
+        video_count: text_content(playlist.video_count_short_text)
+            .and_then(|text| locale.parse_count(&text))
+            .map(|count| count as i32)
+    })
+}
+
+fn parse_lockup(lockup: LockupViewModel, locale: Locale) -> Option<WatchNext> {
+    if lockup.content_id.is_empty() {
+        return None;
+    }
+
+    let kind = match lockup.content_type() {
+        ContentType::LockupContentTypeVideo => WatchNextKind::Video,
+        ContentType::LockupContentTypePlaylist if lockup.content_id.starts_with("RD") => WatchNextKind::Mix,
+        ContentType::LockupContentTypePlaylist => WatchNextKind::Playlist,
+        ContentType::LockupContentTypeUnspecified => return None,
+    };
+
+    let metadata = lockup.metadata.and_then(|metadata| metadata.lockup_metadata_view_model);
+    let title = metadata.as_ref()
+        .and_then(|metadata| metadata.title.as_ref())
+        .map(|title| title.content.trim().to_string())
+        .unwrap_or_default();
+    let rows = metadata
+        .and_then(|metadata| metadata.metadata)
+        .and_then(|metadata| metadata.content_metadata_view_model)
+        .map(|view_model| view_model.metadata_rows)
+        .unwrap_or_default();
+
+    let mut user_id = None;
+    let mut views = None;
+    let mut published_text = None;
+    let mut approx_published_time = None;
+    let mut badges = Vec::new();
+
+    for (index, row) in rows.into_iter().enumerate() {
+        badges.extend(row.badges.into_iter()
+            .filter_map(|badge| badge.badge_view_model)
+            .map(|badge| badge.badge_text)
+            .filter(|text| !text.is_empty()));
+
+        for text in row.metadata_parts.into_iter().filter_map(|part| part.text) {
+            // The first row is the channel, which can have digits in its name
+            if index == 0 {
+                user_id = user_id.or_else(|| text.command_runs.iter()
+                    .filter_map(|run| run.on_tap.as_ref())
+                    .filter_map(|on_tap| on_tap.innertube_command.as_ref())
+                    .filter_map(|command| command.browse_endpoint.as_ref())
+                    .find_map(|browse| browse.browse_id.strip_prefix("UC").map(|id| id.to_string())));
+                continue;
+            }
+
+            let content = text.content.trim();
+            if let Some(timestamp) = locale.parse_relative_time(content) {
+                published_text = Some(content.to_string());
+                approx_published_time = Some(timestamp);
+            } else if kind == WatchNextKind::Video {
+                views = views.or_else(|| locale.parse_count(content));
+            }
+        }
+    }
+
+    let thumbnail = lockup.content_image.and_then(|image| match image.thumbnail_view_model {
+        Some(thumbnail) => Some(thumbnail),
+        None => image.collection_thumbnail_view_model
+            .and_then(|view_model| view_model.primary_thumbnail)
+            .and_then(|thumbnail| thumbnail.thumbnail_view_model),
+    });
+    let thumbnail_badges = thumbnail.into_iter()
+        .flat_map(|thumbnail| thumbnail.overlays)
+        .filter_map(|overlay| overlay.thumbnail_overlay_badge_view_model)
+        .flat_map(|badge| badge.thumbnail_badges)
+        .filter_map(|badge| badge.thumbnail_badge_view_model)
+        .map(|badge| badge.text)
+        .filter(|text| !text.is_empty());
+
+    // e.g. "12:34" on videos and "25 videos" on playlists, anything else is shown as a badge
+    let mut length_seconds = None;
+    let mut video_count = None;
+    for text in thumbnail_badges {
+        let parsed = match kind {
+            WatchNextKind::Video => parse_length_text(&text).map(|length| length_seconds.get_or_insert(length)).is_some(),
+            WatchNextKind::Mix | WatchNextKind::Playlist => locale.parse_count(&text)
+                .map(|count| video_count.get_or_insert(count as i32))
+                .is_some(),
         };
+        if !parsed {
+            badges.push(text);
+        }
+    }
 
-        let mut payload = Vec::new();
-        request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+    let (video_id, playlist_id) = match kind {
+        WatchNextKind::Video => (lockup.content_id, None),
+        WatchNextKind::Mix | WatchNextKind::Playlist => {
+            let video_id = lockup.renderer_context
+                .and_then(|context| context.command_context)
+                .and_then(|context| context.on_tap)
+                .and_then(|on_tap| on_tap.innertube_command)
+                .and_then(|command| command.watch_endpoint)
+                .map(|endpoint| endpoint.video_id)
+                .unwrap_or_default();
+            (video_id, Some(lockup.content_id))
+        }
+    };
 
-        let req = Request::builder()
-            .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/next", self.ip))
-            .header("Host", "youtubei.googleapis.com")
-            .header("Content-Type", "application/x-protobuf")
-            .header("X-Goog-Fieldmask", "contents.twoColumnWatchNextResults(secondaryResults.secondaryResults.results(compactVideoRenderer(videoId,shortBylineText.runs(navigationEndpoint.browseEndpoint.browseId))))")
-            .body(Full::new(Bytes::from(payload)))
-            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+    // Same as compact videos, the channel has to be known to follow a video
+    let user_id = match kind {
+        WatchNextKind::Video => user_id?,
+        WatchNextKind::Mix | WatchNextKind::Playlist => user_id.unwrap_or_default(),
+    };
 
-        let resp = self.client.request(req).await?;
+    Some(WatchNext {
+        user_id,
+        video_id,
+        kind,
+        playlist_id,
+        title,
+        views,
+        length_seconds,
+        published_text,
+        approx_published_time,
+        badges,
+        video_count
+    })
+}
 
-        match resp.status() {
-            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
-            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
-            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
-            StatusCode::OK => Ok(()),
-            status => Err(YouTubeError::UnknownStatusCode(status)),
-        }?;
+fn parse_item(item: Item, locale: Locale) -> Option<WatchNext> {
+    item.compact_video_renderer.and_then(|video| parse_compact_video(video, locale))
+        .or_else(|| item.compact_radio_renderer.and_then(|radio| parse_compact_radio(radio, locale)))
+        .or_else(|| item.compact_playlist_renderer.and_then(|playlist| parse_compact_playlist(playlist, locale)))
+        .or_else(|| item.lockup_view_model.and_then(|lockup| parse_lockup(lockup, locale)))
+}
+
+async fn send_watch_next_request<T: Transport>(
+    client: &InnertubeClient<T>,
+    profile: &ClientProfile,
+    video_id: String,
+    continuation_token: Option<String>
+) -> Result<GetWatchNextResponse, YouTubeError> {
+    let is_continuation = continuation_token.is_some();
+    let locale = profile.locale();
+
+    let request = youtube::NextRequest {
+        context: Some(profile.context()),
+        video_id,
+        continuation: continuation_token,
+    };
+
+    let mut payload = Vec::new();
+    request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+    let fieldmask = match is_continuation {
+        true => format!("onResponseReceivedEndpoints.appendContinuationItemsAction.continuationItems({})", item_fieldmask()),
+        false => format!("contents.twoColumnWatchNextResults(secondaryResults.secondaryResults.results({}))", item_fieldmask()),
+    };
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
-        let response = youtube::NextResponse::decode(body_bytes)?;
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(format!("https://{}/youtubei/v1/next", client.ip))
+        .header("Host", "youtubei.googleapis.com")
+        .header("Content-Type", "application/x-protobuf")
+        .header("X-Goog-Fieldmask", fieldmask)
+        .body(Bytes::from(payload))
+        .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let mut watch_next = Vec::new();
+    let resp = client.send(req, profile).await?;
 
+    match resp.status {
+        StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+        StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+        StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+        StatusCode::OK => Ok(()),
+        status => Err(YouTubeError::UnknownStatusCode(status)),
+    }?;
+
+    let response = youtube::NextResponse::decode(resp.body)?;
+
+    let items: Vec<Item> = match is_continuation {
+        true => response.on_response_received_endpoints
+            .into_iter()
+            .filter_map(|endpoint| endpoint.append_continuation_items_action)
+            .flat_map(|action| action.continuation_items)
+            .collect(),
         // Check for the complete path to results, return WatchNextRendererUnavailable if any part is missing
-        let results = response.contents
+        false => response.contents
             .and_then(|contents| contents.two_column_watch_next_results)
             .and_then(|watch_next_results| watch_next_results.secondary_results)
             .and_then(|secondary_results| secondary_results.secondary_results)
             .map(|secondary_results| secondary_results.results)
-            .ok_or(YouTubeError::WatchNextRendererUnavailable)?;
-
-        // Now we know results exists, process them
-        for result in results {
-            if let Some(video) = result.compact_video_renderer {
-                // Extract channel ID from short byline text
-                if let Some(short_byline) = video.short_byline_text {
-                    if let Some(runs) = short_byline.runs {
-                        if let Some(navigation) = runs.navigation_endpoint {
-                            if let Some(browse) = navigation.browse_endpoint {
-                                // Strip "UC" prefix from browse_id to get user_id
-                                if let Some(user_id) = browse.browse_id.strip_prefix("UC") {
-                                    watch_next.push(WatchNext {
-                                        user_id: user_id.to_string(),
-                                        video_id: video.video_id.clone(),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+            .ok_or(YouTubeError::WatchNextRendererUnavailable)?,
+    };
+
+    let mut watch_next = Vec::new();
+    let mut continuation = None;
+
+    for mut item in items {
+        if let Some(cont_item) = item.continuation_item_renderer.take() {
+            continuation = parse_continuation_item(cont_item).or(continuation);
         }
 
-        Ok(watch_next)
+        if let Some(recommendation) = parse_item(item, locale) {
+            watch_next.push(recommendation);
+        }
+    }
+
+    Ok(GetWatchNextResponse { recommendations: watch_next, continuation })
+}
+
+pub struct GetWatchNextRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub video_id: String,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetWatchNextRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
     }
-}
\ No newline at end of file
+}
+
+/// Every recommendation of a sidebar page, mixes and playlists included.
+pub struct GetWatchNextResponse {
+    pub recommendations: Vec<WatchNext>,
+    pub continuation: Option<String>
+}
+
+impl<T: Transport> GetWatchNextRequest<T> {
+    /// Recommended videos, without the mixes and playlists in between.
+    pub async fn send(self) -> Result<Vec<WatchNext>, YouTubeError> {
+        let response = self.send_page().await?;
+        Ok(response.recommendations.into_iter()
+            .filter(|recommendation| recommendation.kind == WatchNextKind::Video)
+            .collect())
+    }
+
+    /// The whole first page, along with the token `get_watch_next_continued` loads the next one with.
+    pub async fn send_page(self) -> Result<GetWatchNextResponse, YouTubeError> {
+        send_watch_next_request(&self.client, self.client.profile(&self.fields), self.video_id, None).await
+    }
+}
+
+/// Loads more recommendations, as the "Show more" button at the end of the sidebar does.
+pub struct GetWatchNextContinuationRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub continuation_token: String,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetWatchNextContinuationRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> GetWatchNextContinuationRequest<T> {
+    pub async fn send(self) -> Result<GetWatchNextResponse, YouTubeError> {
+        send_watch_next_request(&self.client, self.client.profile(&self.fields), String::new(), Some(self.continuation_token)).await
+    }
+}
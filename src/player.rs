@@ -0,0 +1,267 @@
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
+use hyper::body::Bytes;
+use hyper::{Method, Request, StatusCode};
+use prost::Message;
+use chrono::{DateTime, NaiveDate};
+use futures::stream::{self, StreamExt};
+use crate::client_profile::ClientProfile;
+use crate::locale::TimeUnit;
+use crate::models::{PlayabilityStatus, PublishedTimePrecision, RemovalReason, Video, VideoDetails};
+use youtube::player_response::playability_status::Status;
+
+// Older videos only have a date, newer ones carry a full timestamp with offset
+// e.g. "2024-06-14" or "2024-06-14T08:00:00-07:00"
+pub(crate) fn parse_microformat_date(date: &str) -> Option<i64> {
+    parse_microformat_date_with_unit(date).map(|(timestamp, _)| timestamp)
+}
+
+fn parse_microformat_date_with_unit(date: &str) -> Option<(i64, TimeUnit)> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(date) {
+        return Some((timestamp.timestamp(), TimeUnit::Second));
+    }
+
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| (datetime.and_utc().timestamp(), TimeUnit::Day))
+}
+
+fn parse_playability_status(status: Status, reason: &str, subreason: &str) -> PlayabilityStatus {
+    let text = format!("{} {}", reason, subreason).to_lowercase();
+
+    match status {
+        Status::Ok => return PlayabilityStatus::Ok,
+        Status::LiveStreamOffline => return PlayabilityStatus::LiveStreamOffline,
+        Status::AgeCheckRequired => return PlayabilityStatus::AgeRestricted,
+        Status::ContentCheckRequired => return PlayabilityStatus::ContentWarning,
+        _ => {}
+    }
+
+    // Removal subreasons are sent alongside a generic "Video unavailable", so they're checked first
+    if text.contains("terms of service") {
+        PlayabilityStatus::Removed(RemovalReason::TermsOfService)
+    } else if text.contains("community guidelines") {
+        PlayabilityStatus::Removed(RemovalReason::CommunityGuidelines)
+    } else if text.contains("copyright") {
+        PlayabilityStatus::Removed(RemovalReason::Copyright)
+    } else if text.contains("account associated with this video has been terminated") {
+        PlayabilityStatus::Removed(RemovalReason::AccountTerminated)
+    } else if text.contains("removed by the uploader") {
+        PlayabilityStatus::Removed(RemovalReason::Uploader)
+    } else if text.contains("private") {
+        PlayabilityStatus::Private
+    } else if text.contains("confirm your age") {
+        PlayabilityStatus::AgeRestricted
+    } else if text.contains("in your country") {
+        PlayabilityStatus::GeoBlocked
+    } else if text.contains("unavailable") {
+        PlayabilityStatus::Unavailable
+    } else if status == Status::LoginRequired {
+        PlayabilityStatus::LoginRequired
+    } else {
+        PlayabilityStatus::Unknown(reason.to_string())
+    }
+}
+
+pub struct GetVideoRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub video_id: String,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for GetVideoRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> GetVideoRequest<T> {
+    pub async fn send(self) -> Result<VideoDetails, YouTubeError> {
+        // The playability reasons are only classified in English
+        let profile = self.client.profile_en(&self.fields);
+        let request = youtube::PlayerRequest {
+            context: Some(profile.context()),
+            video_id: self.video_id.clone(),
+        };
+
+        let mut payload = Vec::new();
+        request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/youtubei/v1/player", self.client.ip))
+            .header("Host", "youtubei.googleapis.com")
+            .header("Content-Type", "application/x-protobuf")
+            .header("X-Goog-Fieldmask", "playabilityStatus(status,reason,errorScreen.playerErrorMessageRenderer.subreason),videoDetails(videoId,title,lengthSeconds,keywords,channelId,shortDescription,viewCount,author,isPrivate,isLiveContent,isLive),microformat.playerMicroformatRenderer(availableCountries,isFamilySafe,isUnlisted,category,publishDate,uploadDate)")
+            .body(Bytes::from(payload))
+            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let resp = self.client.send(req, &profile).await?;
+
+        match resp.status {
+            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+            StatusCode::OK => Ok(()),
+            status => Err(YouTubeError::UnknownStatusCode(status)),
+        }?;
+
+        let body_bytes = resp.body;
+        let response = youtube::PlayerResponse::decode(body_bytes)?;
+
+        let playability = response.playability_status
+            .map(|playability| {
+                let subreason = playability.error_screen
+                    .as_ref()
+                    .and_then(|screen| screen.player_error_message_renderer.as_ref())
+                    .and_then(|renderer| renderer.subreason.as_ref())
+                    .map(|subreason| if subreason.simple_text.is_empty() {
+                        subreason.runs.iter().map(|run| run.text.as_str()).collect()
+                    } else {
+                        subreason.simple_text.clone()
+                    })
+                    .unwrap_or_default();
+
+                parse_playability_status(playability.status(), &playability.reason, &subreason)
+            })
+            .unwrap_or(PlayabilityStatus::Ok);
+
+        // Unavailable, private and removed videos come back without details
+        let details = response.video_details.unwrap_or_default();
+        let microformat = response.microformat
+            .and_then(|microformat| microformat.player_microformat_renderer);
+
+        let age_restricted = playability == PlayabilityStatus::AgeRestricted
+            || microformat.as_ref().is_some_and(|microformat| !microformat.is_family_safe);
+
+        let microformat = microformat.unwrap_or_default();
+
+        Ok(VideoDetails {
+            video_id: if details.video_id.is_empty() { self.video_id } else { details.video_id },
+            title: details.title,
+            description: details.short_description,
+            user_id: details.channel_id.strip_prefix("UC").unwrap_or_default().to_string(),
+            author: details.author,
+            views: details.view_count.parse().unwrap_or_default(),
+            length_seconds: details.length_seconds.parse().unwrap_or_default(),
+            keywords: details.keywords,
+            category: Some(microformat.category).filter(|category| !category.is_empty()),
+            published_at: parse_microformat_date(&microformat.publish_date),
+            uploaded_at: parse_microformat_date(&microformat.upload_date),
+            is_live: details.is_live,
+            is_live_content: details.is_live_content,
+            is_private: details.is_private || playability == PlayabilityStatus::Private,
+            is_unlisted: microformat.is_unlisted,
+            age_restricted,
+            playability,
+            available_countries: microformat.available_countries,
+        })
+    }
+}
+
+/// Replaces the approximate publish times of `videos` with the publish date from each video's
+/// player microformat, falling back to the upload date.
+pub struct GetPublishTimesRequest<'a, T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub videos: &'a mut [Video],
+    pub concurrency: usize,
+}
+
+impl<'a, T: Transport> AsMut<InnerTubeRequestFields> for GetPublishTimesRequest<'a, T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+async fn fetch_publish_time<T: Transport>(client: &InnertubeClient<T>, profile: &ClientProfile, video_id: String) -> Result<Option<(i64, TimeUnit)>, YouTubeError> {
+    let request = youtube::PlayerRequest {
+        context: Some(profile.context()),
+        video_id,
+    };
+
+    let mut payload = Vec::new();
+    request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(format!("https://{}/youtubei/v1/player", client.ip))
+        .header("Host", "youtubei.googleapis.com")
+        .header("Content-Type", "application/x-protobuf")
+        .header("X-Goog-Fieldmask", "microformat.playerMicroformatRenderer(publishDate,uploadDate)")
+        .body(Bytes::from(payload))
+        .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+    let resp = client.send(req, profile).await?;
+
+    match resp.status {
+        StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+        StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+        StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+        StatusCode::OK => Ok(()),
+        status => Err(YouTubeError::UnknownStatusCode(status)),
+    }?;
+
+    let response = youtube::PlayerResponse::decode(resp.body)?;
+
+    // Removed and private videos come back without a microformat
+    Ok(response.microformat
+        .and_then(|microformat| microformat.player_microformat_renderer)
+        .and_then(|microformat| parse_microformat_date_with_unit(&microformat.publish_date)
+            .or_else(|| parse_microformat_date_with_unit(&microformat.upload_date))))
+}
+
+impl<'a, T: Transport> GetPublishTimesRequest<'a, T> {
+    /// How many player requests are in flight at once. Defaults to 8.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Returns how many videos got an exact publish time.
+    ///
+    /// Videos that already have one are skipped. A video whose request fails keeps its approximate
+    /// time, the error is only returned if no video could be resolved.
+    pub async fn send(self) -> Result<usize, YouTubeError> {
+        let profile = self.client.profile(&self.fields);
+        let client = &self.client;
+
+        let pending: Vec<(usize, String)> = self.videos.iter()
+            .enumerate()
+            .filter(|(_, video)| !video.published_time_precision.is_some_and(|precision| precision.is_exact()))
+            .map(|(index, video)| (index, video.video_id.clone()))
+            .collect();
+
+        let results: Vec<_> = stream::iter(pending)
+            .map(|(index, video_id)| async move {
+                (index, fetch_publish_time(client, profile, video_id).await)
+            })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await;
+
+        let mut resolved = 0;
+        let mut last_error = None;
+        for (index, result) in results {
+            match result {
+                Ok(Some((timestamp, unit))) => {
+                    let video = &mut self.videos[index];
+                    video.approx_published_time = Some(timestamp);
+                    video.published_time_precision = Some(PublishedTimePrecision::Exact(unit));
+                    resolved += 1;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!("Failed to resolve the publish time of {}: {}", self.videos[index].video_id, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        match last_error {
+            Some(e) if resolved == 0 => Err(e),
+            _ => Ok(resolved),
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use crate::transport::{HyperTransport, Transport, TransportResponse};
+use crate::client_profile::ClientProfile;
+use crate::retry::RetryPolicy;
+use crate::address::AddressAllocator;
+use crate::{ClientError, InnertubeClient, YouTubeError};
+use hyper::body::Bytes;
+use hyper::{Request, StatusCode};
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How `InnertubeClientPool::get` picks the next address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PoolStrategy {
+    /// Cycles through the healthy addresses in order.
+    #[default]
+    RoundRobin,
+    /// Prefers the address that was ratelimited longest ago (or never).
+    LeastRecentlyLimited,
+}
+
+/// What gets taken out of rotation after a 429.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuarantineScope {
+    /// Only the address that was ratelimited.
+    #[default]
+    Address,
+    /// Every address in the same /64 (`range_id`) as the one that was ratelimited.
+    Range,
+}
+
+/// Snapshot of one pooled address, returned by `InnertubeClientPool::stats`.
+#[derive(Debug, Clone)]
+pub struct AddressStats {
+    pub address: IpAddr,
+    pub range_id: u16,
+    pub successes: u64,
+    pub ratelimits: u64,
+    /// Other error statuses and transport errors
+    pub failures: u64,
+    pub last_limited: Option<Instant>,
+    /// Time left until the address is handed out again, `None` if it's healthy
+    pub quarantined_for: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct AddressState {
+    address: IpAddr,
+    range_id: u16,
+    successes: AtomicU64,
+    ratelimits: AtomicU64,
+    failures: AtomicU64,
+    last_limited: Mutex<Option<Instant>>,
+}
+
+impl AddressState {
+    fn last_limited(&self) -> Option<Instant> {
+        *self.last_limited.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn record(&self, result: &Result<TransportResponse, YouTubeError>) {
+        match result {
+            Ok(resp) if resp.status == StatusCode::TOO_MANY_REQUESTS => {
+                self.ratelimits.fetch_add(1, Ordering::Relaxed);
+                *self.last_limited.lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+            }
+            Ok(resp) if resp.status.is_success() => {
+                self.successes.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => {
+                self.failures.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// Transport of a client handed out by `InnertubeClientPool`.
+///
+/// Sends through the transport bound to one pooled address and records the outcome of every
+/// request against that address.
+pub struct PooledTransport<T: Transport = HyperTransport> {
+    inner: Arc<T>,
+    state: Arc<AddressState>,
+}
+
+impl<T: Transport> PooledTransport<T> {
+    pub fn address(&self) -> IpAddr {
+        self.state.address
+    }
+
+    pub fn range_id(&self) -> u16 {
+        self.state.range_id
+    }
+}
+
+impl<T: Transport> Transport for PooledTransport<T> {
+    async fn send(&self, request: Request<Bytes>) -> Result<TransportResponse, YouTubeError> {
+        let result = self.inner.send(request).await;
+        self.state.record(&result);
+        result
+    }
+
+    // The address is owned by the pool, get another client from it instead
+    fn rotate_ipv6(&self) -> bool {
+        false
+    }
+}
+
+struct PoolEntry<T: Transport> {
+    transport: Arc<T>,
+    state: Arc<AddressState>,
+}
+
+/// A set of clients bound to distinct addresses of an IPv6 /48.
+///
+/// Every request made through a pooled client is counted against its address. After a 429 the
+/// address (or its whole /64, see `QuarantineScope`) is skipped by `get` until the cooldown has
+/// passed.
+pub struct InnertubeClientPool<T: Transport = HyperTransport> {
+    entries: Vec<PoolEntry<T>>,
+    ip: String,
+    next: AtomicUsize,
+    strategy: PoolStrategy,
+    scope: QuarantineScope,
+    cooldown: Duration,
+    profile: ClientProfile,
+    retry_policy: RetryPolicy,
+}
+
+impl InnertubeClientPool<HyperTransport> {
+    /// Creates `size` clients, spread over random /64 ranges of `subnet`.
+    pub fn new(subnet: &str, ip: String, size: usize) -> Result<Self, ClientError> {
+        Self::from_allocator(&AddressAllocator::ipv6(subnet)?, ip, size)
+    }
+
+    /// Creates up to `size` clients bound to distinct addresses of the allocator.
+    /// Fewer are created if the allocator only has a handful of IPv4 addresses.
+    pub fn from_allocator(allocator: &AddressAllocator, ip: String, size: usize) -> Result<Self, ClientError> {
+        let transports = allocator.addresses(size)?
+            .into_iter()
+            .map(|(address, range_id)| Ok((address, range_id, HyperTransport::bind(address)?)))
+            .collect::<Result<Vec<_>, ClientError>>()?;
+
+        Ok(Self::from_transports(transports, ip))
+    }
+
+    /// Creates `per_range` clients with distinct addresses in each of the given /64 ranges of `subnet`.
+    pub fn from_ranges(subnet: &str, ip: String, range_ids: &[u16], per_range: usize) -> Result<Self, ClientError> {
+        let allocator = AddressAllocator::ipv6(subnet)?;
+        let mut addresses = HashSet::new();
+        let mut transports = Vec::with_capacity(range_ids.len() * per_range);
+
+        for &range_id in range_ids {
+            for _ in 0..per_range {
+                let address = loop {
+                    let address = allocator.ipv6_address(range_id)?;
+                    if addresses.insert(address) {
+                        break address;
+                    }
+                };
+                transports.push((address, range_id, HyperTransport::bind(address)?));
+            }
+        }
+
+        Ok(Self::from_transports(transports, ip))
+    }
+}
+
+impl<T: Transport> InnertubeClientPool<T> {
+    /// Builds a pool from transports that are already bound to their address.
+    pub fn from_transports(transports: Vec<(IpAddr, u16, T)>, ip: String) -> Self {
+        let entries = transports.into_iter()
+            .map(|(address, range_id, transport)| PoolEntry {
+                transport: Arc::new(transport),
+                state: Arc::new(AddressState {
+                    address,
+                    range_id,
+                    successes: AtomicU64::new(0),
+                    ratelimits: AtomicU64::new(0),
+                    failures: AtomicU64::new(0),
+                    last_limited: Mutex::new(None),
+                }),
+            })
+            .collect();
+
+        InnertubeClientPool {
+            entries,
+            ip,
+            next: AtomicUsize::new(0),
+            strategy: PoolStrategy::default(),
+            scope: QuarantineScope::default(),
+            cooldown: Duration::from_secs(60),
+            profile: ClientProfile::default(),
+            retry_policy: RetryPolicy::none(),
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: PoolStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn with_quarantine_scope(mut self, scope: QuarantineScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// How long an address stays out of rotation after a 429. Defaults to a minute.
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Sets the profile of every client handed out.
+    pub fn with_client_profile(mut self, profile: ClientProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Sets the retry policy of every client handed out. Retries stay on the same address.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // When each entry leaves quarantine, None if it's healthy
+    fn quarantined_until(&self) -> Vec<Option<Instant>> {
+        let now = Instant::now();
+        let last_limited: Vec<Option<Instant>> = self.entries.iter()
+            .map(|entry| entry.state.last_limited())
+            .collect();
+
+        let mut range_limited: HashMap<u16, Instant> = HashMap::new();
+        if self.scope == QuarantineScope::Range {
+            for (entry, limited) in self.entries.iter().zip(&last_limited) {
+                if let Some(limited) = limited {
+                    let latest = range_limited.entry(entry.state.range_id).or_insert(*limited);
+                    *latest = (*latest).max(*limited);
+                }
+            }
+        }
+
+        self.entries.iter()
+            .zip(&last_limited)
+            .map(|(entry, limited)| {
+                let limited = match self.scope {
+                    QuarantineScope::Address => *limited,
+                    QuarantineScope::Range => range_limited.get(&entry.state.range_id).copied(),
+                };
+                limited.map(|limited| limited + self.cooldown).filter(|until| *until > now)
+            })
+            .collect()
+    }
+
+    /// Hands out a client bound to a healthy address, or `None` if every address is quarantined.
+    pub fn get(&self) -> Option<InnertubeClient<PooledTransport<T>>> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let quarantined = self.quarantined_until();
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % self.entries.len();
+        let mut healthy = (0..self.entries.len())
+            .map(|offset| (start + offset) % self.entries.len())
+            .filter(|index| quarantined[*index].is_none());
+
+        let index = match self.strategy {
+            PoolStrategy::RoundRobin => healthy.next(),
+            // Never limited sorts first, ties keep round-robin order
+            PoolStrategy::LeastRecentlyLimited => healthy.min_by_key(|index| self.entries[*index].state.last_limited()),
+        }?;
+
+        let entry = &self.entries[index];
+        let transport = PooledTransport {
+            inner: entry.transport.clone(),
+            state: entry.state.clone(),
+        };
+
+        Some(
+            InnertubeClient::from_transport(transport, self.ip.clone())
+                .with_client_profile(self.profile.clone())
+                .with_retry_policy(self.retry_policy.clone())
+        )
+    }
+
+    /// Per-address counters and quarantine state, in pool order.
+    pub fn stats(&self) -> Vec<AddressStats> {
+        let now = Instant::now();
+
+        self.entries.iter()
+            .zip(self.quarantined_until())
+            .map(|(entry, until)| AddressStats {
+                address: entry.state.address,
+                range_id: entry.state.range_id,
+                successes: entry.state.successes.load(Ordering::Relaxed),
+                ratelimits: entry.state.ratelimits.load(Ordering::Relaxed),
+                failures: entry.state.failures.load(Ordering::Relaxed),
+                last_limited: entry.state.last_limited(),
+                quarantined_for: until.map(|until| until - now),
+            })
+            .collect()
+    }
+}
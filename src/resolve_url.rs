@@ -1,38 +1,208 @@
-use crate::{youtube, InnerTubeRequestFields, YouTubeError};
-use hyper_tls::HttpsConnector;
-use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
 use hyper::body::Bytes;
-use http_body_util::{BodyExt, Full};
-use hyper::{Method, Request, StatusCode};
+use hyper::{Method, Request, StatusCode, Uri};
 use prost::Message;
+use futures::stream::{self, StreamExt};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-#[derive(Debug)]
-pub struct ResolveUrlResult {
-    pub browse_endpoint: Option<String>,
-    pub url_endpoint: Option<String>,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveUrlResult {
+    /// `params` selects a tab, e.g. for "/@MrBeast/videos"
+    Channel { channel_id: String, params: Option<String> },
+    Video { video_id: String, start_time: Option<i32> },
+    Playlist { playlist_id: String },
+    Shorts { video_id: String },
+    /// Not a YouTube page
+    External(String),
+    NotFound,
+}
+
+impl ResolveUrlResult {
+    pub fn channel_id(&self) -> Option<&str> {
+        match self {
+            ResolveUrlResult::Channel { channel_id, .. } => Some(channel_id),
+            _ => None,
+        }
+    }
+}
+
+const YOUTUBE_HOSTS: &[&str] = &[
+    "youtube.com", "www.youtube.com", "m.youtube.com", "music.youtube.com",
+    "youtube-nocookie.com", "www.youtube-nocookie.com",
+];
+
+fn is_video_id(id: &str) -> bool {
+    id.len() == 11 && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn is_channel_id(id: &str) -> bool {
+    id.len() == 24 && id.starts_with("UC") && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+// e.g. "90", "90s" or "1h2m3s"
+fn parse_start_time(value: &str) -> Option<i32> {
+    if let Ok(seconds) = value.parse::<i32>() {
+        return Some(seconds);
+    }
+
+    let mut seconds: i32 = 0;
+    let mut number = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let amount: i32 = number.parse().ok()?;
+        number.clear();
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        // Anything past i32::MAX seconds isn't a real timestamp
+        seconds = amount.checked_mul(unit).and_then(|amount| seconds.checked_add(amount))?;
+    }
+
+    number.is_empty().then_some(seconds)
+}
+
+fn query_param(query: Option<&str>, name: &str) -> Option<String> {
+    query?.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .and_then(|(_, value)| urlencoding::decode(value).ok())
+        .map(|value| value.into_owned())
+        .filter(|value| !value.is_empty())
+}
+
+/// Classifies a URL without a network call. Returns `None` for links that only the API can
+/// resolve, such as handles, "/c/" and "/user/" links, and anything it doesn't recognize.
+pub fn classify_url(url: &str) -> Option<ResolveUrlResult> {
+    let url = url.trim();
+    let uri: Uri = match url.contains("://") {
+        true => url.parse().ok()?,
+        // Without a scheme, "@handle" or a bare name would be taken for the host
+        false if url.split('/').next().is_some_and(|host| host.contains('.') && !host.contains('@')) => {
+            format!("https://{}", url).parse().ok()?
+        }
+        false => return None,
+    };
+    let host = uri.host()?.to_ascii_lowercase();
+    let query = uri.query();
+    let segments: Vec<&str> = uri.path().split('/').filter(|segment| !segment.is_empty()).collect();
+    let start_time = || query_param(query, "t")
+        .or_else(|| query_param(query, "start"))
+        .and_then(|value| parse_start_time(&value));
+
+    if host == "youtu.be" {
+        return match segments[..] {
+            [video_id] if is_video_id(video_id) => Some(ResolveUrlResult::Video {
+                video_id: video_id.to_string(),
+                start_time: start_time(),
+            }),
+            _ => None,
+        };
+    }
+
+    if !YOUTUBE_HOSTS.contains(&host.as_str()) {
+        return Some(ResolveUrlResult::External(url.to_string()));
+    }
+
+    match segments[..] {
+        ["watch"] => match query_param(query, "v") {
+            Some(video_id) if is_video_id(&video_id) => Some(ResolveUrlResult::Video { video_id, start_time: start_time() }),
+            Some(_) => None,
+            None => query_param(query, "list").map(|playlist_id| ResolveUrlResult::Playlist { playlist_id }),
+        },
+        ["embed" | "live" | "v", video_id] if is_video_id(video_id) => Some(ResolveUrlResult::Video {
+            video_id: video_id.to_string(),
+            start_time: start_time(),
+        }),
+        ["shorts", video_id] if is_video_id(video_id) => Some(ResolveUrlResult::Shorts { video_id: video_id.to_string() }),
+        ["playlist"] => query_param(query, "list").map(|playlist_id| ResolveUrlResult::Playlist { playlist_id }),
+        ["channel", channel_id, ..] if is_channel_id(channel_id) => Some(ResolveUrlResult::Channel {
+            channel_id: channel_id.to_string(),
+            params: None,
+        }),
+        _ => None,
+    }
+}
+
+// Share and referral parameters that don't change where a link points
+const TRACKING_PARAMS: &[&str] = &["si", "feature", "pp", "app", "fbclid", "gclid"];
+
+/// Turns "@handle", "c/name", "youtube.com/user/name?si=..." and the like into a full URL such as
+/// "https://www.youtube.com/@handle". Tracking parameters, fragments and trailing slashes are
+/// removed and non-ASCII characters are percent-encoded, so equivalent inputs normalize to the same URL.
+pub fn normalize_url(input: &str) -> String {
+    let input = input.trim();
+    if input.is_empty() {
+        return String::new();
+    }
+
+    let rest = input.strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))
+        .unwrap_or(input);
+    let rest = rest.split('#').next().unwrap_or_default();
+
+    // Anything that doesn't start with a host, e.g. "@handle", "/c/name" or "MrBeast"
+    let host_end = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (host, rest) = match rest[..host_end].contains('.') && !rest.starts_with('@') {
+        true => (rest[..host_end].to_ascii_lowercase(), &rest[host_end..]),
+        false => ("www.youtube.com".to_string(), rest),
+    };
+    let host = match host.as_str() {
+        "youtube.com" | "m.youtube.com" => "www.youtube.com".to_string(),
+        _ => host,
+    };
+
+    let (path, query) = match rest.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (rest, None),
+    };
+
+    let mut encoded_path = String::new();
+    for c in path.trim_matches('/').chars() {
+        match c.is_ascii() && c != ' ' {
+            true => encoded_path.push(c),
+            false => encoded_path.push_str(&urlencoding::encode(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+
+    let query: Vec<&str> = query.into_iter()
+        .flat_map(|query| query.split('&'))
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default();
+            !key.is_empty() && !TRACKING_PARAMS.contains(&key) && !key.starts_with("utm_")
+        })
+        .collect();
+
+    match query.is_empty() {
+        true => format!("https://{}/{}", host, encoded_path),
+        false => format!("https://{}/{}?{}", host, encoded_path, query.join("&")),
+    }
 }
 
-pub struct ResolveUrlRequest<'a> {
-    pub client: &'a mut Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
-    pub ip: &'a str,
-    pub fields: InnerTubeRequestFields<'a>,
+pub struct ResolveUrlRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
     pub url: String,
 }
 
-impl<'a> AsMut<InnerTubeRequestFields<'a>> for ResolveUrlRequest<'a> {
-    fn as_mut(&mut self) -> &mut InnerTubeRequestFields<'a> {
+impl<T: Transport> AsMut<InnerTubeRequestFields> for ResolveUrlRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
         &mut self.fields
     }
 }
 
-impl<'a> ResolveUrlRequest<'a> {
+impl<T: Transport> ResolveUrlRequest<T> {
 
-    pub async fn send(self) -> Result<Option<ResolveUrlResult>, YouTubeError> {
+    pub async fn send(self) -> Result<ResolveUrlResult, YouTubeError> {
         let request = youtube::ResolveUrlRequest {
-            context: Some(youtube::Context {
-                client: Some(youtube::Client { client_name: 1, client_version: "2.20240614.01.00".to_string() })
-            }),
+            context: Some(self.client.context(&self.fields)),
             url: self.url
         };
 
@@ -41,58 +211,188 @@ impl<'a> ResolveUrlRequest<'a> {
 
         let req = Request::builder()
             .method(Method::POST)
-            .uri(format!("https://{}/youtubei/v1/navigation/resolve_url", self.ip))
+            .uri(format!("https://{}/youtubei/v1/navigation/resolve_url", self.client.ip))
             .header("Host", "youtubei.googleapis.com")
             .header("Content-Type", "application/x-protobuf")
-            .header("X-Goog-Fieldmask", "endpoint.browseEndpoint.browseId,endpoint.urlEndpoint.url")
-            .body(Full::new(Bytes::from(payload)))
+            .header("X-Goog-Fieldmask", "endpoint(browseEndpoint(browseId,params),urlEndpoint.url,watchEndpoint(videoId,playlistId,startTimeSeconds),reelWatchEndpoint.videoId)")
+            .body(Bytes::from(payload))
             .map_err(|e| YouTubeError::Other(Box::new(e)))?;
 
-        let resp = self.client.request(req).await?;
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
 
-        match resp.status() {
-            StatusCode::NOT_FOUND => return Ok(None), // Return None for 404
+        match resp.status {
+            StatusCode::NOT_FOUND => return Ok(ResolveUrlResult::NotFound),
             StatusCode::TOO_MANY_REQUESTS => return Err(YouTubeError::Ratelimited),
             StatusCode::UNAUTHORIZED => return Err(YouTubeError::Unauthorized),
             StatusCode::OK => (), // Continue processing
             status => return Err(YouTubeError::UnknownStatusCode(status)),
         };
 
-        let body_bytes = resp.into_body().collect().await?.to_bytes();
+        let body_bytes = resp.body;
         let response = youtube::ResolveUrlResponse::decode(body_bytes)?;
 
         let endpoint = match response.endpoint {
             Some(ep) => ep,
-            None => return Ok(None),
+            None => return Ok(ResolveUrlResult::NotFound),
         };
 
-        let browse_endpoint = endpoint.browse_endpoint
-            .and_then(|browse_endpoint| {
-                if browse_endpoint.browse_id.is_empty() {
-                    None
-                } else {
-                    Some(browse_endpoint.browse_id)
-                }
-            });
-
-        let url_endpoint = endpoint.url_endpoint
-            .and_then(|url_endpoint| {
-                if url_endpoint.url.is_empty() {
-                    None
-                } else {
-                    Some(url_endpoint.url)
-                }
-            });
-
-        if browse_endpoint.is_none() && url_endpoint.is_none() {
-            Ok(None)
-        } else {
-            Ok(Some(ResolveUrlResult {
-                browse_endpoint,
-                url_endpoint,
-            }))
+        if let Some(reel) = endpoint.reel_watch_endpoint.filter(|reel| !reel.video_id.is_empty()) {
+            return Ok(ResolveUrlResult::Shorts { video_id: reel.video_id });
         }
-    
+
+        if let Some(watch) = endpoint.watch_endpoint {
+            if !watch.video_id.is_empty() {
+                return Ok(ResolveUrlResult::Video {
+                    video_id: watch.video_id,
+                    start_time: Some(watch.start_time_seconds).filter(|seconds| *seconds > 0),
+                });
+            }
+            if !watch.playlist_id.is_empty() {
+                return Ok(ResolveUrlResult::Playlist { playlist_id: watch.playlist_id });
+            }
+        }
+
+        if let Some(browse) = endpoint.browse_endpoint {
+            if let Some(playlist_id) = browse.browse_id.strip_prefix("VL") {
+                return Ok(ResolveUrlResult::Playlist { playlist_id: playlist_id.to_string() });
+            }
+            // Other browse ids are feeds such as "FEtrending"
+            if browse.browse_id.starts_with("UC") {
+                return Ok(ResolveUrlResult::Channel {
+                    channel_id: browse.browse_id,
+                    params: Some(browse.params).filter(|params| !params.is_empty()),
+                });
+            }
+        }
+
+        match endpoint.url_endpoint.filter(|url_endpoint| !url_endpoint.url.is_empty()) {
+            Some(url_endpoint) => Ok(ResolveUrlResult::External(url_endpoint.url)),
+            None => Ok(ResolveUrlResult::NotFound),
+        }
+    }
+
+}
+
+/// Resolved URLs keyed by normalized URL, shared between `resolve_many` calls with `with_cache`.
+/// Errors aren't cached.
+#[derive(Debug, Default)]
+pub struct ResolveCache {
+    entries: Mutex<HashMap<String, ResolveUrlResult>>,
+}
+
+impl ResolveCache {
+    pub fn new() -> Self {
+        ResolveCache::default()
+    }
+
+    pub fn get(&self, url: &str) -> Option<ResolveUrlResult> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).get(url).cloned()
+    }
+
+    pub fn insert(&self, url: String, result: ResolveUrlResult) {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).insert(url, result);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).clear();
     }
+}
+
+#[derive(Debug)]
+pub struct ResolveManyResult {
+    pub input: String,
+    /// What was resolved, see `normalize_url`
+    pub url: String,
+    /// `Ok(ResolveUrlResult::NotFound)` if the URL doesn't point anywhere, `Err` if it couldn't be
+    /// resolved. Inputs that normalize to the same URL share the error.
+    pub result: Result<ResolveUrlResult, Arc<YouTubeError>>,
+}
 
-}
\ No newline at end of file
+/// Resolves a list of handles and URLs, one result per input in the same order.
+///
+/// Inputs are normalized first, and inputs that normalize to the same URL are resolved once.
+/// Links that can be classified locally, e.g. "/channel/UC..." or "watch?v=", don't need a request.
+pub struct ResolveManyRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub urls: Vec<String>,
+    pub concurrency: usize,
+    pub cache: Option<Arc<ResolveCache>>,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for ResolveManyRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> ResolveManyRequest<T> {
+    /// How many URLs are resolved at once. Defaults to 8.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Arc<ResolveCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    async fn resolve(&self, url: String) -> Result<ResolveUrlResult, YouTubeError> {
+        if url.is_empty() {
+            return Ok(ResolveUrlResult::NotFound);
+        }
+        if let Some(result) = self.cache.as_ref().and_then(|cache| cache.get(&url)) {
+            return Ok(result);
+        }
+
+        let result = match classify_url(&url) {
+            Some(result) => result,
+            None => ResolveUrlRequest {
+                client: self.client.clone(),
+                fields: self.fields.clone(),
+                url: url.clone(),
+            }.send().await?,
+        };
+
+        if let Some(cache) = &self.cache {
+            cache.insert(url, result.clone());
+        }
+        Ok(result)
+    }
+
+    pub async fn send(self) -> Vec<ResolveManyResult> {
+        let inputs: Vec<(String, String)> = self.urls.iter()
+            .map(|input| (input.clone(), normalize_url(input)))
+            .collect();
+
+        let mut unique: Vec<String> = inputs.iter().map(|(_, url)| url.clone()).collect();
+        unique.sort();
+        unique.dedup();
+
+        let this = &self;
+        let resolved: HashMap<String, Result<ResolveUrlResult, Arc<YouTubeError>>> = stream::iter(unique)
+            .map(|url| async move {
+                let result = this.resolve(url.clone()).await.map_err(Arc::new);
+                (url, result)
+            })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await;
+
+        inputs.into_iter()
+            .map(|(input, url)| {
+                let result = resolved.get(&url).cloned().unwrap_or(Ok(ResolveUrlResult::NotFound));
+                ResolveManyResult { input, url, result }
+            })
+            .collect()
+    }
+}
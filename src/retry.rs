@@ -0,0 +1,232 @@
+use crate::transport::{Transport, TransportResponse};
+use crate::YouTubeError;
+use hyper::body::Bytes;
+use hyper::{Request, StatusCode};
+use rand::Rng;
+use std::error::Error;
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Controls how `InnertubeClient` retries requests that failed for transient reasons.
+///
+/// Ratelimits (429), server errors (5xx) and dropped connections are retried with exponential
+/// backoff and jitter. With `rotate_ipv6` set, a fresh address is picked from the client's subnet
+/// before every retry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one. 1 disables retrying.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub retry_ratelimited: bool,
+    pub retry_server_errors: bool,
+    pub retry_connection_errors: bool,
+    pub rotate_ipv6: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            retry_ratelimited: true,
+            retry_server_errors: true,
+            retry_connection_errors: true,
+            rotate_ipv6: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Sends every request exactly once, errors are returned as they are.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_backoff(mut self, base_delay: Duration, max_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_retry_ratelimited(mut self, retry: bool) -> Self {
+        self.retry_ratelimited = retry;
+        self
+    }
+
+    pub fn with_retry_server_errors(mut self, retry: bool) -> Self {
+        self.retry_server_errors = retry;
+        self
+    }
+
+    pub fn with_retry_connection_errors(mut self, retry: bool) -> Self {
+        self.retry_connection_errors = retry;
+        self
+    }
+
+    pub fn with_rotate_ipv6(mut self, rotate: bool) -> Self {
+        self.rotate_ipv6 = rotate;
+        self
+    }
+
+    fn is_retryable_status(&self, status: StatusCode) -> bool {
+        (self.retry_ratelimited && status == StatusCode::TOO_MANY_REQUESTS)
+            || (self.retry_server_errors && status.is_server_error())
+    }
+
+    // Exponential backoff with equal jitter: half the delay is fixed, the other half random
+    fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let exponential = self.base_delay
+            .saturating_mul(1u32 << (attempt - 1).min(16))
+            .min(self.max_delay);
+        let half = exponential / 2;
+        let jitter = rand::thread_rng().gen_range(0..=half.as_millis() as u64);
+        let delay = half + Duration::from_millis(jitter);
+
+        // Honour Retry-After, but never wait longer than the policy allows
+        retry_after.map_or(delay, |retry_after| delay.max(retry_after).min(self.max_delay))
+    }
+
+    pub(crate) async fn send<T: Transport>(&self, transport: &T, request: Request<Bytes>, counter: &RetryCounter) -> Result<TransportResponse, YouTubeError> {
+        let mut attempt = 1;
+
+        loop {
+            counter.attempts.fetch_add(1, Ordering::Relaxed);
+            if attempt == 1 {
+                counter.requests.fetch_add(1, Ordering::Relaxed);
+            } else if attempt == 2 {
+                counter.retried.fetch_add(1, Ordering::Relaxed);
+            }
+
+            let result = transport.send(clone_request(&request)).await;
+
+            let retry_after = match &result {
+                Ok(resp) if self.is_retryable_status(resp.status) => parse_retry_after(resp),
+                Err(e) if self.retry_connection_errors && is_connection_error(e) => None,
+                _ => {
+                    if attempt > 1 {
+                        tracing::debug!("Request to {} finished after {} attempts", request.uri().path(), attempt);
+                    }
+                    return result;
+                }
+            };
+
+            if attempt >= self.max_attempts {
+                // Without retries the response is handed back untouched
+                if attempt == 1 {
+                    return result;
+                }
+
+                let last_error = match result {
+                    Ok(resp) => status_error(resp.status),
+                    Err(e) => e,
+                };
+                return Err(YouTubeError::RetriesExhausted { attempts: attempt, last_error: Box::new(last_error) });
+            }
+
+            let delay = self.delay(attempt, retry_after);
+            match &result {
+                Ok(resp) => tracing::warn!("Attempt {} to {} returned {}, retrying in {:?}", attempt, request.uri().path(), resp.status, delay),
+                Err(e) => tracing::warn!("Attempt {} to {} failed: {}, retrying in {:?}", attempt, request.uri().path(), e, delay),
+            }
+
+            if self.rotate_ipv6 {
+                transport.rotate_ipv6();
+            }
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Attempts made by a client and its clones since it was created, see `InnertubeClient::retry_stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetryStats {
+    /// Requests sent, each counted once no matter how often it was retried.
+    pub requests: u64,
+    /// Attempts over all requests, equal to `requests` while nothing was retried.
+    pub attempts: u64,
+    /// Requests that needed more than one attempt, whether they succeeded or not.
+    pub retried: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct RetryCounter {
+    requests: AtomicU64,
+    attempts: AtomicU64,
+    retried: AtomicU64,
+}
+
+impl RetryCounter {
+    pub(crate) fn stats(&self) -> RetryStats {
+        RetryStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            attempts: self.attempts.load(Ordering::Relaxed),
+            retried: self.retried.load(Ordering::Relaxed),
+        }
+    }
+}
+
+// Request<Bytes> isn't Clone, but the body is reference counted so copying it is cheap
+fn clone_request(request: &Request<Bytes>) -> Request<Bytes> {
+    let mut clone = Request::new(request.body().clone());
+    *clone.method_mut() = request.method().clone();
+    *clone.uri_mut() = request.uri().clone();
+    *clone.version_mut() = request.version();
+    *clone.headers_mut() = request.headers().clone();
+    *clone.extensions_mut() = request.extensions().clone();
+    clone
+}
+
+fn parse_retry_after(resp: &TransportResponse) -> Option<Duration> {
+    resp.headers
+        .get("Retry-After")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs)
+}
+
+fn status_error(status: StatusCode) -> YouTubeError {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS => YouTubeError::Ratelimited,
+        StatusCode::INTERNAL_SERVER_ERROR => YouTubeError::InternalServerError,
+        status => YouTubeError::UnknownStatusCode(status),
+    }
+}
+
+// Connection resets surface as io errors somewhere in hyper's source chain
+fn is_connection_error(error: &YouTubeError) -> bool {
+    match error {
+        YouTubeError::LegacyHttpError(e) if e.is_connect() => true,
+        YouTubeError::HttpError(e) if e.is_incomplete_message() || e.is_closed() => true,
+        YouTubeError::HttpError(_) | YouTubeError::LegacyHttpError(_) => {
+            let mut source = error.source();
+            while let Some(e) = source {
+                if let Some(io_error) = e.downcast_ref::<io::Error>() {
+                    return matches!(
+                        io_error.kind(),
+                        io::ErrorKind::ConnectionReset
+                            | io::ErrorKind::ConnectionAborted
+                            | io::ErrorKind::BrokenPipe
+                            | io::ErrorKind::UnexpectedEof
+                            | io::ErrorKind::TimedOut
+                    );
+                }
+                source = e.source();
+            }
+            false
+        }
+        _ => false,
+    }
+}
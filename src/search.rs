@@ -0,0 +1,352 @@
+use crate::{youtube, InnerTubeRequestFields, InnertubeClient, YouTubeError};
+use crate::transport::{HyperTransport, Transport};
+use hyper::body::Bytes;
+use hyper::{Method, Request, StatusCode};
+use prost::Message;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use crate::models::{ChannelStub, PlaylistStub, Video};
+use crate::locale::Locale;
+use crate::browse::videos::{parse_approx_published_time, parse_length_text, parse_view_count_text, BLACKLISTED_BADGE_LABELS};
+use youtube::search_response::item_section_renderer::contents::channel_renderer::owner_badge::metadata_badge_renderer::Style;
+
+const SEARCH_RESULTS_FIELDMASK: &str = "itemSectionRenderer.contents(videoRenderer(videoId,viewCountText.simpleText,lengthText.simpleText,publishedTimeText.simpleText,badges,upcomingEventData),channelRenderer(channelId,title.simpleText,videoCountText.simpleText,subscriberCountText.simpleText,ownerBadges.metadataBadgeRenderer.style),playlistRenderer(playlistId,title.simpleText,videoCount,shortBylineText.runs.navigationEndpoint.browseEndpoint.browseId)),continuationItemRenderer.continuationEndpoint.continuationCommand.token";
+
+#[derive(Debug, Clone, Copy)]
+pub enum SearchType {
+    Video,
+    Channel,
+    Playlist,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum UploadDate {
+    LastHour,
+    Today,
+    ThisWeek,
+    ThisMonth,
+    ThisYear,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum VideoDuration {
+    // Under 4 minutes
+    Short,
+    // 4 - 20 minutes
+    Medium,
+    // Over 20 minutes
+    Long,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SortBy {
+    Relevance,
+    Rating,
+    UploadDate,
+    ViewCount,
+}
+
+#[derive(Debug, Clone)]
+pub enum SearchResult {
+    Video(Video),
+    Channel(ChannelStub),
+    Playlist(PlaylistStub),
+}
+
+pub fn encode_search_params(
+    search_type: Option<SearchType>,
+    upload_date: Option<UploadDate>,
+    duration: Option<VideoDuration>,
+    sort_by: Option<SortBy>
+) -> Option<String> {
+    use youtube::search_params::{filters, Filters, SortBy as ProtoSortBy};
+
+    if search_type.is_none() && upload_date.is_none() && duration.is_none() && sort_by.is_none() {
+        return None;
+    }
+
+    let filters = if search_type.is_some() || upload_date.is_some() || duration.is_some() {
+        Some(Filters {
+            upload_date: match upload_date {
+                None => filters::UploadDate::Any,
+                Some(UploadDate::LastHour) => filters::UploadDate::LastHour,
+                Some(UploadDate::Today) => filters::UploadDate::Today,
+                Some(UploadDate::ThisWeek) => filters::UploadDate::ThisWeek,
+                Some(UploadDate::ThisMonth) => filters::UploadDate::ThisMonth,
+                Some(UploadDate::ThisYear) => filters::UploadDate::ThisYear,
+            } as i32,
+            r#type: match search_type {
+                None => filters::Type::Any,
+                Some(SearchType::Video) => filters::Type::Video,
+                Some(SearchType::Channel) => filters::Type::Channel,
+                Some(SearchType::Playlist) => filters::Type::Playlist,
+            } as i32,
+            duration: match duration {
+                None => filters::Duration::Any,
+                Some(VideoDuration::Short) => filters::Duration::Short,
+                Some(VideoDuration::Medium) => filters::Duration::Medium,
+                Some(VideoDuration::Long) => filters::Duration::Long,
+            } as i32,
+        })
+    } else {
+        None
+    };
+
+    let params = youtube::SearchParams {
+        sort_by: match sort_by.unwrap_or(SortBy::Relevance) {
+            SortBy::Relevance => ProtoSortBy::SortRelevance,
+            SortBy::Rating => ProtoSortBy::SortRating,
+            SortBy::UploadDate => ProtoSortBy::SortUploadDate,
+            SortBy::ViewCount => ProtoSortBy::SortViewCount,
+        } as i32,
+        filters,
+    };
+
+    Some(urlencoding::encode(&BASE64.encode(params.encode_to_vec())).into_owned())
+}
+
+fn parse_item_section(section: youtube::search_response::ItemSectionRenderer, results: &mut Vec<SearchResult>, locale: Locale) {
+    for content in section.contents {
+        if let Some(video) = content.video_renderer {
+            if video.upcoming_event_data.is_none() && !video.video_id.is_empty() {
+                let (views, hidden_view_count) = parse_view_count_text(video.view_count_text.as_ref().map(|text| text.simple_text.as_str()), locale);
+
+                // Get the first non-blacklisted badge label
+                let badge = video.badges.iter()
+                    .filter_map(|badge| badge.metadata_badge_renderer.as_ref())
+                    .find(|renderer| !BLACKLISTED_BADGE_LABELS.contains(renderer.label.as_str()))
+                    .map(|renderer| renderer.label.clone());
+
+                let length_seconds = video.length_text
+                    .as_ref()
+                    .and_then(|lt| parse_length_text(&lt.simple_text));
+
+                let (approx_published_time, published_time_precision) = parse_approx_published_time(
+                    video.published_time_text.as_ref().map(|text| text.simple_text.as_str()),
+                    locale
+                );
+
+                results.push(SearchResult::Video(Video {
+                    video_id: video.video_id,
+                    views,
+                    hidden_view_count,
+                    badge,
+                    length_seconds,
+                    approx_published_time,
+                    published_time_precision
+                }));
+            }
+        }
+
+        if let Some(channel) = content.channel_renderer {
+            if let Some(user_id) = channel.channel_id.strip_prefix("UC") {
+                // Current layouts show the handle where the video count used to be
+                let handle = channel.video_count_text
+                    .as_ref()
+                    .and_then(|text| text.simple_text.strip_prefix('@'))
+                    .map(|handle| handle.to_string());
+
+                let subscribers = channel.subscriber_count_text
+                    .as_ref()
+                    .and_then(|text| locale.parse_count(&text.simple_text));
+
+                let verified = channel.owner_badges.iter()
+                    .filter_map(|badge| badge.metadata_badge_renderer.as_ref())
+                    .any(|renderer| matches!(renderer.style(), Style::BadgeStyleTypeVerified | Style::BadgeStyleTypeVerifiedArtist));
+
+                results.push(SearchResult::Channel(ChannelStub {
+                    user_id: user_id.to_string(),
+                    display_name: channel.title.map(|title| title.simple_text).unwrap_or_default(),
+                    handle,
+                    subscribers,
+                    verified
+                }));
+            }
+        }
+
+        if let Some(playlist) = content.playlist_renderer {
+            if !playlist.playlist_id.is_empty() {
+                let user_id = playlist.short_byline_text
+                    .and_then(|byline| byline.runs.into_iter().next())
+                    .and_then(|run| run.navigation_endpoint)
+                    .and_then(|endpoint| endpoint.browse_endpoint)
+                    .and_then(|browse| browse.browse_id.strip_prefix("UC").map(|id| id.to_string()));
+
+                results.push(SearchResult::Playlist(PlaylistStub {
+                    playlist_id: playlist.playlist_id,
+                    title: playlist.title.map(|title| title.simple_text).unwrap_or_default(),
+                    user_id,
+                    video_count: playlist.video_count.parse().ok()
+                }));
+            }
+        }
+    }
+}
+
+fn parse_continuation_item(item: youtube::search_response::ContinuationItemRenderer) -> Option<String> {
+    item.continuation_endpoint
+        .and_then(|endpoint| endpoint.continuation_command)
+        .map(|command| command.token)
+        .filter(|token| !token.is_empty())
+}
+
+pub struct SearchRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub query: String,
+    pub search_type: Option<SearchType>,
+    pub upload_date: Option<UploadDate>,
+    pub duration: Option<VideoDuration>,
+    pub sort_by: Option<SortBy>,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for SearchRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> SearchRequest<T> {
+    pub fn with_type(mut self, search_type: SearchType) -> Self {
+        self.search_type = Some(search_type);
+        self
+    }
+
+    pub fn with_upload_date(mut self, upload_date: UploadDate) -> Self {
+        self.upload_date = Some(upload_date);
+        self
+    }
+
+    pub fn with_duration(mut self, duration: VideoDuration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    pub fn with_sort_by(mut self, sort_by: SortBy) -> Self {
+        self.sort_by = Some(sort_by);
+        self
+    }
+
+    pub async fn send(self) -> Result<(Vec<SearchResult>, Option<String>), YouTubeError> {
+        let locale = self.client.locale(&self.fields);
+
+        let request = youtube::SearchRequest {
+            context: Some(self.client.context(&self.fields)),
+            query: self.query,
+            params: encode_search_params(self.search_type, self.upload_date, self.duration, self.sort_by),
+            continuation: None,
+        };
+
+        let mut payload = Vec::new();
+        request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/youtubei/v1/search", self.client.ip))
+            .header("Host", "youtubei.googleapis.com")
+            .header("Content-Type", "application/x-protobuf")
+            .header("X-Goog-Fieldmask", format!("contents.twoColumnSearchResultsRenderer.primaryContents.sectionListRenderer.contents({})", SEARCH_RESULTS_FIELDMASK))
+            .body(Bytes::from(payload))
+            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
+
+        match resp.status {
+            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+            StatusCode::OK => Ok(()),
+            status => Err(YouTubeError::UnknownStatusCode(status)),
+        }?;
+
+        let response = youtube::SearchResponse::decode(resp.body)?;
+
+        let mut results = Vec::new();
+        let mut continuation = None;
+
+        if let Some(section_list) = response.contents
+            .and_then(|contents| contents.two_column_search_results_renderer)
+            .and_then(|renderer| renderer.primary_contents)
+            .and_then(|primary| primary.section_list_renderer)
+        {
+            for content in section_list.contents {
+                if let Some(section) = content.item_section_renderer {
+                    parse_item_section(section, &mut results, locale);
+                }
+
+                if let Some(cont_item) = content.continuation_item_renderer {
+                    continuation = parse_continuation_item(cont_item).or(continuation);
+                }
+            }
+        }
+
+        Ok((results, continuation))
+    }
+}
+
+pub struct SearchContinuationRequest<T: Transport = HyperTransport> {
+    pub client: InnertubeClient<T>,
+    pub fields: InnerTubeRequestFields,
+    pub continuation_token: String,
+}
+
+impl<T: Transport> AsMut<InnerTubeRequestFields> for SearchContinuationRequest<T> {
+    fn as_mut(&mut self) -> &mut InnerTubeRequestFields {
+        &mut self.fields
+    }
+}
+
+impl<T: Transport> SearchContinuationRequest<T> {
+    pub async fn send(self) -> Result<(Vec<SearchResult>, Option<String>), YouTubeError> {
+        let locale = self.client.locale(&self.fields);
+
+        let request = youtube::SearchRequest {
+            context: Some(self.client.context(&self.fields)),
+            query: String::new(),
+            params: None,
+            continuation: Some(self.continuation_token),
+        };
+
+        let mut payload = Vec::new();
+        request.encode(&mut payload).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/youtubei/v1/search", self.client.ip))
+            .header("Host", "youtubei.googleapis.com")
+            .header("Content-Type", "application/x-protobuf")
+            .header("X-Goog-Fieldmask", format!("onResponseReceivedCommands.appendContinuationItemsAction.continuationItems({})", SEARCH_RESULTS_FIELDMASK))
+            .body(Bytes::from(payload))
+            .map_err(|e| YouTubeError::Other(Box::new(e)))?;
+
+        let resp = self.client.send(req, self.client.profile(&self.fields)).await?;
+
+        match resp.status {
+            StatusCode::NOT_FOUND => Err(YouTubeError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(YouTubeError::Ratelimited),
+            StatusCode::UNAUTHORIZED => Err(YouTubeError::Unauthorized),
+            StatusCode::OK => Ok(()),
+            status => Err(YouTubeError::UnknownStatusCode(status)),
+        }?;
+
+        let response = youtube::SearchResponse::decode(resp.body)?;
+
+        let mut results = Vec::new();
+        let mut continuation = None;
+
+        if let Some(action) = response.on_response_received_commands
+            .and_then(|commands| commands.append_continuation_items_action)
+        {
+            for item in action.continuation_items {
+                if let Some(section) = item.item_section_renderer {
+                    parse_item_section(section, &mut results, locale);
+                }
+
+                if let Some(cont_item) = item.continuation_item_renderer {
+                    continuation = parse_continuation_item(cont_item).or(continuation);
+                }
+            }
+        }
+
+        Ok((results, continuation))
+    }
+}
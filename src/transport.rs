@@ -0,0 +1,367 @@
+use crate::{ClientError, YouTubeError};
+use hyper::body::Bytes;
+use hyper::{HeaderMap, Request, StatusCode};
+use hyper_tls::HttpsConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::client::legacy::connect::HttpConnector;
+use http_body_util::{BodyExt, Full};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::future::Future;
+use std::io;
+use std::net::IpAddr;
+use std::path::Path;
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use crate::address::AddressAllocator;
+use crate::proxy::{ProxiedClient, Proxy};
+
+/// A fully buffered response returned by a [`Transport`].
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+/// Sends InnerTube requests over the wire.
+///
+/// `InnertubeClient` is generic over this trait so the HTTP stack can be swapped out
+/// (rustls, reqwest, a recording/replaying transport or an in-process fake for tests).
+pub trait Transport: Send + Sync {
+    fn send(&self, request: Request<Bytes>) -> impl Future<Output = Result<TransportResponse, YouTubeError>> + Send;
+
+    /// Switches to a fresh source address for the following requests.
+    /// Returns false if the transport isn't bound to a subnet.
+    fn rotate_ipv6(&self) -> bool {
+        false
+    }
+}
+
+/// Default transport backed by a hyper client over native-tls.
+pub struct HyperTransport {
+    // Swapped out when rotating to a new address, requests in flight keep the old client
+    client: RwLock<Client<HttpsConnector<HttpConnector>, Full<Bytes>>>,
+    allocator: Option<AddressAllocator>,
+    // One client per proxy so connections through it are reused
+    proxied: Mutex<HashMap<Proxy, ProxiedClient>>,
+}
+
+impl HyperTransport {
+    pub fn new(subnet: Option<&str>, subnet_id: Option<u16>) -> Result<Self, ClientError> {
+        let allocator = subnet.map(AddressAllocator::ipv6).transpose()?;
+        let address = allocator.as_ref()
+            .map(|allocator| allocator.ipv6_address(subnet_id.unwrap_or_default()))
+            .transpose()?;
+
+        Ok(HyperTransport {
+            client: RwLock::new(crate::initialize_client_with_address(address)?),
+            allocator,
+            proxied: Mutex::default(),
+        })
+    }
+
+    /// Starts on a random address of the allocator, `rotate_ipv6` draws new ones from it.
+    pub fn from_allocator(allocator: AddressAllocator) -> Result<Self, ClientError> {
+        let address = allocator.random_address()?;
+
+        Ok(HyperTransport {
+            client: RwLock::new(crate::initialize_client_with_address(Some(address))?),
+            allocator: Some(allocator),
+            proxied: Mutex::default(),
+        })
+    }
+
+    /// Binds every connection to `address`. The address stays fixed, `rotate_ipv6` is a no-op.
+    pub fn bind(address: IpAddr) -> Result<Self, ClientError> {
+        Ok(HyperTransport {
+            client: RwLock::new(crate::initialize_client_with_address(Some(address))?),
+            allocator: None,
+            proxied: Mutex::default(),
+        })
+    }
+
+    /// Sets the subnet `rotate_ipv6` picks new addresses from.
+    pub fn with_subnet(mut self, subnet: Option<&str>) -> Self {
+        self.allocator = subnet
+            .filter(|subnet| !subnet.is_empty())
+            .and_then(|subnet| AddressAllocator::ipv6(subnet)
+                .inspect_err(|e| tracing::error!("Ignoring subnet {}: {}", subnet, e))
+                .ok());
+        self
+    }
+
+    fn client(&self) -> Client<HttpsConnector<HttpConnector>, Full<Bytes>> {
+        self.client.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn proxied_client(&self, proxy: &Proxy) -> Result<ProxiedClient, ClientError> {
+        let mut proxied = self.proxied.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(client) = proxied.get(proxy) {
+            return Ok(client.clone());
+        }
+
+        let client = proxy.client()?;
+        proxied.insert(proxy.clone(), client.clone());
+        Ok(client)
+    }
+}
+
+impl Clone for HyperTransport {
+    fn clone(&self) -> Self {
+        HyperTransport {
+            client: RwLock::new(self.client()),
+            allocator: self.allocator.clone(),
+            proxied: Mutex::new(self.proxied.lock().unwrap_or_else(|e| e.into_inner()).clone()),
+        }
+    }
+}
+
+impl From<Client<HttpsConnector<HttpConnector>, Full<Bytes>>> for HyperTransport {
+    fn from(client: Client<HttpsConnector<HttpConnector>, Full<Bytes>>) -> Self {
+        HyperTransport { client: RwLock::new(client), allocator: None, proxied: Mutex::default() }
+    }
+}
+
+impl Transport for HyperTransport {
+    async fn send(&self, request: Request<Bytes>) -> Result<TransportResponse, YouTubeError> {
+        // Requests carrying a `Proxy` extension go through that proxy instead of the local address
+        let resp = match request.extensions().get::<Proxy>() {
+            Some(proxy) => {
+                let client = self.proxied_client(proxy).map_err(|e| YouTubeError::Other(Box::new(e)))?;
+                client.request(request.map(Full::new)).await?
+            }
+            None => self.client().request(request.map(Full::new)).await?,
+        };
+
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let body = resp.into_body().collect().await?.to_bytes();
+
+        Ok(TransportResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    fn rotate_ipv6(&self) -> bool {
+        let Some(allocator) = &self.allocator else {
+            return false;
+        };
+
+        let client = allocator.random_address()
+            .map_err(ClientError::from)
+            .and_then(|address| crate::initialize_client_with_address(Some(address)));
+        match client {
+            Ok(client) => {
+                *self.client.write().unwrap_or_else(|e| e.into_inner()) = client;
+                true
+            }
+            Err(e) => {
+                tracing::error!("Failed to rotate IPv6 address: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// A recorded response served by [`ReplayTransport`].
+///
+/// A fixture matches a request when the path equals `endpoint`, the `X-Goog-Fieldmask` header
+/// contains `fieldmask` (if set), the request goes through the `proxy` host (if set) and the raw
+/// request body contains every `request_contains` string.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fixture {
+    pub endpoint: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fieldmask: Option<String>,
+    #[serde(default)]
+    pub request_contains: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    #[serde(default = "default_fixture_status")]
+    pub status: u16,
+    /// Path of the response body, relative to the manifest. No body is served if unset.
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub source: FixtureSource,
+}
+
+/// Where the body of a [`Fixture`] came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FixtureSource {
+    /// Recorded from the live API by [`RecordingTransport`]
+    Captured,
+    /// Written by hand, e.g. for edge cases that can't be captured on demand
+    #[default]
+    HandWritten,
+}
+
+fn default_fixture_status() -> u16 {
+    200
+}
+
+/// Offline transport that answers requests from recorded fixtures instead of the network.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayTransport {
+    fixtures: Vec<(Fixture, Bytes)>,
+}
+
+impl ReplayTransport {
+    pub fn new() -> Self {
+        ReplayTransport::default()
+    }
+
+    /// Loads every fixture listed in a JSON manifest. Fixtures are matched in manifest order.
+    pub fn from_manifest(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        let fixtures: Vec<Fixture> = serde_json::from_slice(&fs::read(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut transport = ReplayTransport::new();
+        for fixture in fixtures {
+            let body = match &fixture.body {
+                Some(file) => Bytes::from(fs::read(base.join(file))?),
+                None => Bytes::new(),
+            };
+            transport = transport.with_fixture(fixture, body);
+        }
+
+        Ok(transport)
+    }
+
+    pub fn with_fixture(mut self, fixture: Fixture, body: Bytes) -> Self {
+        self.fixtures.push((fixture, body));
+        self
+    }
+
+    fn find(&self, request: &Request<Bytes>) -> Option<&(Fixture, Bytes)> {
+        let fieldmask = request.headers()
+            .get("X-Goog-Fieldmask")
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        let proxy = request.extensions().get::<Proxy>().map(|proxy| proxy.host.as_str());
+        let body = request.body().as_ref();
+
+        self.fixtures.iter().find(|(fixture, _)| {
+            fixture.endpoint == request.uri().path()
+                && fixture.fieldmask.as_ref().is_none_or(|mask| fieldmask.contains(mask.as_str()))
+                && fixture.proxy.as_deref().is_none_or(|host| proxy == Some(host))
+                && fixture.request_contains.iter().all(|needle| {
+                    needle.is_empty() || body.windows(needle.len()).any(|window| window == needle.as_bytes())
+                })
+        })
+    }
+}
+
+impl Transport for ReplayTransport {
+    async fn send(&self, request: Request<Bytes>) -> Result<TransportResponse, YouTubeError> {
+        let (fixture, body) = self.find(&request).ok_or_else(|| {
+            YouTubeError::Other(Box::new(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No fixture recorded for {}", request.uri().path())
+            )))
+        })?;
+
+        Ok(TransportResponse {
+            status: StatusCode::from_u16(fixture.status)
+                .map_err(|e| YouTubeError::Other(Box::new(e)))?,
+            headers: HeaderMap::new(),
+            body: body.clone(),
+        })
+    }
+}
+
+/// Passes requests on to another transport and keeps every response, so real responses can be
+/// captured once and replayed by [`ReplayTransport`].
+///
+/// Recorded fixtures match on the endpoint and the full fieldmask, plus each of the `keys`
+/// (e.g. video, channel or playlist ids) found in the request body.
+pub struct RecordingTransport<T: Transport = HyperTransport> {
+    inner: T,
+    keys: Vec<String>,
+    recorded: Mutex<Vec<(Fixture, Bytes)>>,
+}
+
+impl<T: Transport> RecordingTransport<T> {
+    pub fn new(inner: T) -> Self {
+        RecordingTransport {
+            inner,
+            keys: Vec::new(),
+            recorded: Mutex::default(),
+        }
+    }
+
+    pub fn with_keys(mut self, keys: Vec<String>) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Everything recorded so far, in the order the responses arrived.
+    pub fn fixtures(&self) -> Vec<(Fixture, Bytes)> {
+        self.recorded.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Writes every recorded body into `dir`, along with a `manifest.json` that
+    /// `ReplayTransport::from_manifest` loads.
+    pub fn save(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        let mut manifest = Vec::new();
+        for (index, (mut fixture, body)) in self.fixtures().into_iter().enumerate() {
+            if !body.is_empty() {
+                // JSON bodies are kept readable, e.g. "next_003.json" or "browse_000.bin"
+                let extension = if body.starts_with(b"{") { "json" } else { "bin" };
+                let name = fixture.endpoint.rsplit('/').next().unwrap_or("response");
+                let file = format!("{}_{:03}.{}", name, index, extension);
+                fs::write(dir.join(&file), &body)?;
+                fixture.body = Some(file);
+            }
+            manifest.push(fixture);
+        }
+
+        let manifest = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(dir.join("manifest.json"), manifest)
+    }
+}
+
+impl<T: Transport> Transport for RecordingTransport<T> {
+    async fn send(&self, request: Request<Bytes>) -> Result<TransportResponse, YouTubeError> {
+        let endpoint = request.uri().path().to_string();
+        let fieldmask = request.headers()
+            .get("X-Goog-Fieldmask")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+        let proxy = request.extensions().get::<Proxy>().map(|proxy| proxy.host.clone());
+        let body = request.body().as_ref();
+        let request_contains = self.keys.iter()
+            .filter(|key| !key.is_empty() && body.windows(key.len()).any(|window| window == key.as_bytes()))
+            .cloned()
+            .collect();
+
+        let response = self.inner.send(request).await?;
+
+        let fixture = Fixture {
+            endpoint,
+            fieldmask,
+            request_contains,
+            proxy,
+            status: response.status.as_u16(),
+            body: None,
+            source: FixtureSource::Captured,
+        };
+        self.recorded.lock().unwrap_or_else(|e| e.into_inner()).push((fixture, response.body.clone()));
+
+        Ok(response)
+    }
+
+    fn rotate_ipv6(&self) -> bool {
+        self.inner.rotate_ipv6()
+    }
+}
@@ -6,9 +6,9 @@ use prost::Message;
 use crate::youtube::channel_continuation::Token;
 use crate::youtube::ChannelContinuation;
 use chrono::NaiveDate;
-use std::net::{IpAddr, Ipv6Addr};
-use rand::Rng;
-use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::net::IpAddr;
+use crate::address::{AddressAllocator, AddressError};
+use chrono::{Duration, Utc};
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -27,43 +27,16 @@ pub enum ClientError {
     TlsError(#[from] native_tls::Error),
 }
 
-pub fn get_rand_ipv6(subnet: &str, range_id: u16) -> Result<IpAddr, Box<dyn std::error::Error>> {
-    // Split the subnet string into address and prefix length
-    let parts: Vec<&str> = subnet.split('/').collect();
-    if parts.len() != 2 {
-        return Err("Invalid subnet format".into());
-    }
-
-    // Parse the IPv6 address
-    let ipv6: u128 = parts[0].parse::<Ipv6Addr>()?.into();
-
-    // Parse the prefix length
-    let prefix_len: u8 = parts[1].parse()?;
-    if prefix_len != 48 {
-        return Err("Only /48 subnets are supported".into());
-    }
-
-    // Clear the lower 80 bits (128 - 48) of the network address
-    let net_part = (ipv6 >> 80) << 80;
-    
-    // Shift the range_id into position (place it in bits 64-79)
-    let range_part = (range_id as u128) << 64;
-    
-    // Generate random number for host portion (lower 64 bits)
-    let rand: u128 = rand::thread_rng().gen();
-    let host_part = rand & ((1u128 << 64) - 1);  // Only keep lower 64 bits
-    
-    // Combine all parts
-    let result = net_part | range_part | host_part;
-
-    Ok(IpAddr::V6(result.into()))
+/// A random address in the given /64 (`range_id`) of an IPv6 subnet, see [`AddressAllocator`].
+pub fn get_rand_ipv6(subnet: &str, range_id: u16) -> Result<IpAddr, AddressError> {
+    AddressAllocator::ipv6(subnet)?.ipv6_address(range_id)
 }
 
 pub fn parse_numeric_string(numeric_str: &str) -> i64 {
     let numeric_str = numeric_str.trim();
     let parts: Vec<&str> = numeric_str.split_whitespace().collect();
 
-    if parts.len() >= 1 {
+    if !parts.is_empty() {
         let value_str = parts[0].replace(",", "");
         value_str.parse().unwrap_or(0)
     } else {
@@ -78,17 +51,18 @@ pub fn parse_multiplied_string(multiplied_str: &str) -> i64 {
     let multiplier: i64;
     let value: f64;
 
-    if multiplied_str.ends_with('K') {
+    if let Some(number) = multiplied_str.strip_suffix('K') {
         multiplier = 1000;
-        value = multiplied_str[..multiplied_str.len() - 1].parse().unwrap_or(0.0);
-    } else if multiplied_str.ends_with('M') {
+        value = number.parse().unwrap_or(0.0);
+    } else if let Some(number) = multiplied_str.strip_suffix('M') {
         multiplier = 1_000_000;
-        value = multiplied_str[..multiplied_str.len() - 1].parse().unwrap_or(0.0);
-    } else if multiplied_str.ends_with('B') {
+        value = number.parse().unwrap_or(0.0);
+    } else if let Some(number) = multiplied_str.strip_suffix('B') {
         multiplier = 1_000_000_000;
-        value = multiplied_str[..multiplied_str.len() - 1].parse().unwrap_or(0.0);
+        value = number.parse().unwrap_or(0.0);
     } else {
-        return multiplied_str.parse().unwrap_or(0);
+        // Exact counts are comma separated (e.g. "12,345")
+        return multiplied_str.replace(',', "").parse().unwrap_or(0);
     }
 
     (value * multiplier as f64) as i64
@@ -96,7 +70,7 @@ pub fn parse_multiplied_string(multiplied_str: &str) -> i64 {
 
 pub fn parse_creation_date(joined_date_str: &str) -> i32 {
     let parts: Vec<&str> = joined_date_str.split_whitespace().collect();
-    if parts.len() == 0 {
+    if parts.is_empty() {
         return 0
     }
 
@@ -0,0 +1,16 @@
+#!/bin/sh
+# Re-encodes every *.txtpb fixture into the binary response body served by ReplayTransport.
+# The message type is read from the "# proto-message:" header of each file.
+# Endpoints that answer with base64 text (creator API) are marked with "# response-encoding: base64".
+set -e
+cd "$(dirname "$0")/../.."
+
+for fixture in tests/fixtures/*/*.txtpb; do
+    message=$(sed -n 's/^# proto-message: //p' "$fixture")
+    ${PROTOC:-protoc} -I . --encode="$message" innertube/*.proto < "$fixture" > "${fixture%.txtpb}.bin"
+
+    if grep -q "^# response-encoding: base64" "$fixture"; then
+        base64 -w0 < "${fixture%.txtpb}.bin" > "${fixture%.txtpb}.bin.tmp"
+        mv "${fixture%.txtpb}.bin.tmp" "${fixture%.txtpb}.bin"
+    fi
+done